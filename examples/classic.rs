@@ -1,8 +1,10 @@
+use std::io;
+
 use fancy_table::{
     charset::Charset, Align, FancyTable, FancyTableOpts, Layout, Separator, TitleAlign,
 };
 
-fn main() {
+fn main() -> io::Result<()> {
     let table = FancyTable::create(FancyTableOpts {
         charset: Charset::Classic,
         ..Default::default()
@@ -32,5 +34,5 @@ fn main() {
             "Renegade\n10/10",
             "Maeglin is an elf who betrayed his fellow elves to the evil Morgoth in an age before The Lord of the Rings.",
         ]
-    ]);
+    ])
 }
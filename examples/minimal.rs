@@ -1,6 +1,8 @@
+use std::io;
+
 use fancy_table::{charset::Charset, Align, FancyTable, FancyTableOpts, Layout, Separator};
 
-fn main() {
+fn main() -> io::Result<()> {
     let table = FancyTable::create(FancyTableOpts {
         charset: Charset::Minimal,
         max_lines: 5,
@@ -29,5 +31,5 @@ fn main() {
             "Tearjerker\n1/10",
             "Tauriel is a woodland elf created for The Hobbit films. Her name means \"daughter of the forest\" in Sindarin.",
         ]
-    ]);
+    ])
 }
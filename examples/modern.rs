@@ -1,6 +1,8 @@
+use std::io;
+
 use fancy_table::{Align, FancyTable, FancyTableOpts, Layout, Separator, TitleAlign};
 
-fn main() {
+fn main() -> io::Result<()> {
     let table = FancyTable::create(FancyTableOpts::default())
         .add_title_with_align("props", TitleAlign::RightOffset(1))
         .add_column_named("ID", Layout::Slim)
@@ -28,5 +30,5 @@ fn main() {
             "Tearjerker\n1/10",
             "Tauriel is a woodland elf created for The Hobbit films. Her name means \"daughter of the forest\" in Sindarin.",
         ]
-    ]);
+    ])
 }
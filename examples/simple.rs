@@ -1,8 +1,10 @@
+use std::io;
+
 use fancy_table::{
     charset::Charset, Align, FancyTable, FancyTableOpts, Layout, Separator, TitleAlign,
 };
 
-fn main() {
+fn main() -> io::Result<()> {
     let table = FancyTable::create(FancyTableOpts {
         charset: Charset::Simple,
         ..Default::default()
@@ -32,5 +34,5 @@ fn main() {
             "Tearjerker\n1/10",
             "Tauriel is a woodland elf created for The Hobbit films. Her name means \"daughter of the forest\" in Sindarin.",
         ]
-    ]);
+    ])
 }
@@ -0,0 +1,275 @@
+//! `#[derive(FancyRow)]` generating columns of a table and conversion of
+//! struct into table row. Each named field becomes a column, configured
+//! with optional `#[table(...)]` attribute:
+//!
+//! - `header = "NAME"` - column header, field name in upper case by default
+//! - `layout = "fixed(16)"` - one of `slim` (default), `fixed(n)`,
+//!   `expandable(n)`, `percent(n)`, `fraction(n)` or `range(min, max)`
+//! - `align = "center"` - one of `left`, `right`, `center` or `decimal`,
+//!   derived from type of the field by default
+//! - `wrap` - wraps text instead of truncating it
+//! - `skip` - leaves the field out of the table
+//! - `display` - renders field with its `Display` implementation
+//! - `format(precision = 2, thousands = ',', units = "iec")` - formatter
+//!   of typed cells, may be given as a string too:
+//!   `format = "precision = 2, thousands = ','"`
+//!
+//! Tables created with `FancyRow::table` are fit to their content, so that
+//! columns with default `slim` layout are as wide as their widest value.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    meta::ParseNestedMeta, parse_macro_input, Data, DeriveInput, Error, Field, Fields, LitChar,
+    LitInt, LitStr, Result, Token,
+};
+
+#[proc_macro_derive(FancyRow, attributes(table))]
+pub fn derive_fancy_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Column {
+    header: Option<String>,
+    layout: Option<TokenStream2>,
+    align: Option<TokenStream2>,
+    format: Option<Vec<TokenStream2>>,
+    wrap: bool,
+    skip: bool,
+    display: bool,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(name, "FancyRow requires named fields")),
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                "FancyRow can be derived for structs only",
+            ))
+        }
+    };
+
+    let mut columns = Vec::new();
+    let mut cells = Vec::new();
+
+    for field in fields {
+        let column = Column::parse(field)?;
+        if column.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let header = column
+            .header
+            .unwrap_or_else(|| ident.to_string().to_uppercase());
+        let layout = column
+            .layout
+            .unwrap_or_else(|| quote!(::fancy_table::Layout::Slim));
+        let align = column.align.unwrap_or_else(|| match column.display {
+            true => quote!(::fancy_table::Align::Left),
+            false => quote!(<#ty as ::fancy_table::cell::IntoCell>::align()),
+        });
+        let add = match column.wrap {
+            true => quote!(add_column_named_wrapping_with_align),
+            false => quote!(add_column_named_with_align),
+        };
+        let format = column.format.map(|calls| {
+            quote! {
+                let col = builder.column_count() - 1;
+                builder = builder.column_format(col, ::fancy_table::cell::CellFormat::new() #(.#calls)*);
+            }
+        });
+        columns.push(quote! {
+            builder = builder.#add(#header, #layout, #align);
+            #format
+        });
+        cells.push(match column.display {
+            true => quote!(::fancy_table::cell::Cell::display(&self.#ident)),
+            false => quote!(::fancy_table::cell::IntoCell::into_cell(
+                ::core::clone::Clone::clone(&self.#ident)
+            )),
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::fancy_table::FancyRow for #name #ty_generics #where_clause {
+            fn add_columns<'a>(
+                builder: ::fancy_table::FancyTableBuilder<'a, &'static str>,
+            ) -> ::fancy_table::FancyTableBuilder<'a, &'static str> {
+                #[allow(unused_mut)]
+                let mut builder = builder;
+                #(#columns)*
+                builder
+            }
+
+            fn to_cells(&self) -> ::std::vec::Vec<::fancy_table::cell::Cell> {
+                ::std::vec![#(#cells),*]
+            }
+        }
+    })
+}
+
+impl Column {
+    fn parse(field: &Field) -> Result<Self> {
+        let mut column = Column::default();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("table")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("header") {
+                    column.header = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("layout") {
+                    column.layout = Some(parse_layout(&meta.value()?.parse()?)?);
+                } else if meta.path.is_ident("align") {
+                    column.align = Some(parse_align(&meta.value()?.parse()?)?);
+                } else if meta.path.is_ident("format") {
+                    column.format = Some(parse_format(&meta)?);
+                } else if meta.path.is_ident("wrap") {
+                    column.wrap = true;
+                } else if meta.path.is_ident("skip") {
+                    column.skip = true;
+                } else if meta.path.is_ident("display") {
+                    column.display = true;
+                } else {
+                    return Err(meta.error("unsupported table attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(column)
+    }
+}
+
+/// Parses layout given as `name` or `name(args)`, eg. `fixed(16)`.
+fn parse_layout(lit: &LitStr) -> Result<TokenStream2> {
+    let value = lit.value().replace(' ', "");
+    let (name, args) = match value.split_once('(') {
+        Some((name, rest)) => match rest.strip_suffix(')') {
+            Some(args) => (name.to_owned(), args.split(',').collect::<Vec<_>>()),
+            None => return Err(Error::new_spanned(lit, "unclosed layout arguments")),
+        },
+        None => (value.clone(), Vec::new()),
+    };
+    let args = args
+        .iter()
+        .map(|arg| arg.parse::<usize>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| Error::new_spanned(lit, "layout arguments must be numbers"))?;
+
+    match (name.as_str(), args.as_slice()) {
+        ("slim", []) => Ok(quote!(::fancy_table::Layout::Slim)),
+        ("fixed", [n]) => Ok(quote!(::fancy_table::Layout::Fixed(#n))),
+        ("expandable", [n]) => Ok(quote!(::fancy_table::Layout::Expandable(#n))),
+        ("fraction", [n]) => Ok(quote!(::fancy_table::Layout::Fraction(#n))),
+        ("percent", [n]) => {
+            let n =
+                u8::try_from(*n).map_err(|_| Error::new_spanned(lit, "percent out of range"))?;
+            Ok(quote!(::fancy_table::Layout::Percent(#n)))
+        }
+        ("range", [min, max]) => Ok(quote!(::fancy_table::Layout::Range {
+            min: #min,
+            max: #max
+        })),
+        _ => Err(Error::new_spanned(lit, "unknown layout")),
+    }
+}
+
+fn parse_align(lit: &LitStr) -> Result<TokenStream2> {
+    match lit.value().as_str() {
+        "left" => Ok(quote!(::fancy_table::Align::Left)),
+        "right" => Ok(quote!(::fancy_table::Align::Right)),
+        "center" => Ok(quote!(::fancy_table::Align::Center)),
+        "decimal" => Ok(quote!(::fancy_table::Align::Decimal('.'))),
+        _ => Err(Error::new_spanned(lit, "unknown alignment")),
+    }
+}
+
+/// Parses formatter given either as `format(...)` list or as a string
+/// with the same list, eg. `format = "precision = 2"`, into calls of
+/// `CellFormat` builder methods.
+fn parse_format(meta: &ParseNestedMeta) -> Result<Vec<TokenStream2>> {
+    let mut calls = Vec::new();
+    if meta.input.peek(Token![=]) {
+        let list = meta.value()?.parse::<LitStr>()?;
+        list.parse_with(syn::meta::parser(|meta| format_call(meta, &mut calls)))?;
+    } else {
+        meta.parse_nested_meta(|meta| format_call(meta, &mut calls))?;
+    }
+    Ok(calls)
+}
+
+fn format_call(meta: ParseNestedMeta, calls: &mut Vec<TokenStream2>) -> Result<()> {
+    if meta.path.is_ident("precision") {
+        let digits = meta.value()?.parse::<LitInt>()?;
+        calls.push(quote!(precision(#digits)));
+    } else if meta.path.is_ident("thousands") {
+        let separator = meta.value()?.parse::<LitChar>()?;
+        calls.push(quote!(thousands(#separator)));
+    } else if meta.path.is_ident("units") {
+        let units = meta.value()?.parse::<LitStr>()?;
+        let units = match units.value().as_str() {
+            "si" => quote!(::fancy_table::cell::Units::Si),
+            "iec" => quote!(::fancy_table::cell::Units::Iec),
+            _ => return Err(Error::new_spanned(units, "units must be either si or iec")),
+        };
+        calls.push(quote!(units(#units)));
+    } else {
+        return Err(meta.error("unsupported format attribute"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use syn::parse_quote;
+
+    fn error(input: DeriveInput) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn reject_invalid_attributes() {
+        let layout = error(parse_quote! {
+            struct Row { #[table(layout = "wide(3)")] name: String }
+        });
+        assert_eq!(layout, "unknown layout");
+
+        let args = error(parse_quote! {
+            struct Row { #[table(layout = "fixed(x)")] name: String }
+        });
+        assert_eq!(args, "layout arguments must be numbers");
+
+        let percent = error(parse_quote! {
+            struct Row { #[table(layout = "percent(300)")] name: String }
+        });
+        assert_eq!(percent, "percent out of range");
+
+        let units = error(parse_quote! {
+            struct Row { #[table(format(units = "metric"))] size: u64 }
+        });
+        assert_eq!(units, "units must be either si or iec");
+
+        let units = error(parse_quote! {
+            struct Row { #[table(format = "units = \"metric\"")] size: u64 }
+        });
+        assert_eq!(units, "units must be either si or iec");
+
+        let align = error(parse_quote! {
+            struct Row { #[table(align = "justify")] name: String }
+        });
+        assert_eq!(align, "unknown alignment");
+
+        let tuple = error(parse_quote! { struct Row(String); });
+        assert_eq!(tuple, "FancyRow requires named fields");
+    }
+}
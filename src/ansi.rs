@@ -0,0 +1,178 @@
+use std::borrow::Cow;
+
+pub const RESET: &str = "\x1b[0m";
+
+pub enum Segment<'a> {
+    Text(&'a str),
+    Escape(&'a str),
+}
+
+pub struct Segments<'a> {
+    rest: &'a str,
+}
+
+/// Active SGR (Select Graphic Rendition) sequences, ie. colors and text
+/// attributes which have been opened and not reset yet.
+#[derive(Clone, Default)]
+pub struct SgrState<'a> {
+    seqs: Vec<&'a str>,
+}
+
+/// Splits given string into plain text and escape sequences.
+pub fn segments(s: &str) -> Segments<'_> {
+    Segments { rest: s }
+}
+
+/// Removes all escape sequences from given string.
+pub fn strip(s: &str) -> Cow<'_, str> {
+    if !s.contains('\x1b') {
+        return Cow::Borrowed(s);
+    }
+    segments(s)
+        .filter_map(|seg| match seg {
+            Segment::Text(t) => Some(t),
+            Segment::Escape(_) => None,
+        })
+        .collect()
+}
+
+/// Returns length (in bytes) of escape sequence at the beginning of `s`.
+/// Unterminated sequences swallow the rest of string to never leak
+/// partial escapes into the output.
+fn escape_len(s: &str) -> usize {
+    let b = s.as_bytes();
+    match b.get(1) {
+        Some(b'[') => {
+            let params = b[2..]
+                .iter()
+                .take_while(|c| (0x20..=0x3f).contains(*c))
+                .count();
+            match b.get(2 + params) {
+                Some(c) if (0x40..=0x7e).contains(c) => 3 + params,
+                _ => b.len(),
+            }
+        }
+        Some(b']') => {
+            for i in 2..b.len() {
+                match b[i] {
+                    0x07 => return i + 1,
+                    0x1b if b.get(i + 1) == Some(&b'\\') => return i + 2,
+                    _ => (),
+                }
+            }
+            b.len()
+        }
+        Some(c) if (0x20..=0x7e).contains(c) => 2,
+        _ => 1,
+    }
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = Segment<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let len = match self.rest.find('\x1b') {
+            Some(0) => escape_len(self.rest),
+            Some(pos) => pos,
+            None => self.rest.len(),
+        };
+        let (head, tail) = self.rest.split_at(len);
+        self.rest = tail;
+
+        if head.starts_with('\x1b') {
+            Some(Segment::Escape(head))
+        } else {
+            Some(Segment::Text(head))
+        }
+    }
+}
+
+impl<'a> SgrState<'a> {
+    /// Updates state with all SGR sequences found in given string.
+    pub fn feed(&mut self, s: &'a str) {
+        for seg in segments(s) {
+            if let Segment::Escape(esc) = seg {
+                self.apply(esc);
+            }
+        }
+    }
+
+    fn apply(&mut self, esc: &'a str) {
+        if !(esc.starts_with("\x1b[") && esc.ends_with('m')) {
+            return;
+        }
+        let params = &esc[2..esc.len() - 1];
+        let first = params.split([';', ':']).next().unwrap_or_default();
+
+        if first.bytes().all(|b| b == b'0') {
+            self.seqs.clear();
+            if !params.contains(';') {
+                return;
+            }
+        }
+        self.seqs.push(esc);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seqs.is_empty()
+    }
+
+    /// Returns sequences re-opening all active styles.
+    pub fn prefix(&self) -> String {
+        self.seqs.concat()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn collect(s: &str) -> Vec<(bool, &str)> {
+        segments(s)
+            .map(|seg| match seg {
+                Segment::Text(t) => (false, t),
+                Segment::Escape(e) => (true, e),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn split_into_segments() {
+        assert_eq!(
+            collect("a\x1b[1;31mred\x1b[0m"),
+            vec![
+                (false, "a"),
+                (true, "\x1b[1;31m"),
+                (false, "red"),
+                (true, "\x1b[0m")
+            ]
+        );
+        assert_eq!(
+            collect("\x1b]8;;http://x\x07link"),
+            vec![(true, "\x1b]8;;http://x\x07"), (false, "link")]
+        );
+        assert_eq!(collect("ok\x1b[31"), vec![(false, "ok"), (true, "\x1b[31")]);
+    }
+
+    #[test]
+    fn strip_escapes() {
+        assert_eq!(strip("\x1b[1;31mred\x1b[0m alert"), "red alert");
+        assert!(matches!(strip("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn track_active_styles() {
+        let mut state = SgrState::default();
+        state.feed("\x1b[1mbold \x1b[31mred");
+        assert_eq!(state.prefix(), "\x1b[1m\x1b[31m");
+
+        state.feed("\x1b[0;4m");
+        assert_eq!(state.prefix(), "\x1b[0;4m");
+
+        state.feed("\x1b[m");
+        assert!(state.is_empty());
+    }
+}
@@ -0,0 +1,382 @@
+use std::{borrow::Cow, io, time::Duration};
+
+use crate::{export::export, Align, FancyTable};
+
+/// Typed value of a table cell, turned into text by formatter of its column.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Cell {
+    Text(String),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Bool(bool),
+    Duration(Duration),
+    /// Size in bytes, always formatted with units.
+    Bytes(u64),
+    /// Missing value, rendered as null marker of the table.
+    Null,
+}
+
+/// Conversion of values into table cells.
+pub trait IntoCell {
+    fn into_cell(self) -> Cell;
+
+    /// Alignment of columns holding values of this type.
+    fn align() -> Align
+    where
+        Self: Sized,
+    {
+        Align::Left
+    }
+}
+
+/// Multiples of units used to shorten large numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Units {
+    /// Powers of 1000: k, M, G...
+    Si,
+    /// Powers of 1024: Ki, Mi, Gi...
+    Iec,
+}
+
+/// Formatter of typed cells of a column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CellFormat {
+    precision: Option<usize>,
+    thousands: Option<char>,
+    units: Option<Units>,
+}
+
+impl Cell {
+    /// Makes a text cell of any displayable value.
+    pub fn display(value: impl std::fmt::Display) -> Self {
+        Self::Text(value.to_string())
+    }
+
+    /// Makes a cell of size in bytes.
+    pub fn bytes(size: u64) -> Self {
+        Self::Bytes(size)
+    }
+}
+
+impl IntoCell for Cell {
+    fn into_cell(self) -> Cell {
+        self
+    }
+}
+
+macro_rules! into_cell {
+    ($variant:ident($as:ty), $align:expr, $($t:ty),+) => {
+        $(impl IntoCell for $t {
+            fn into_cell(self) -> Cell {
+                Cell::$variant(self as $as)
+            }
+            fn align() -> Align {
+                $align
+            }
+        })+
+    };
+}
+
+into_cell!(Int(i64), Align::Right, i8, i16, i32, i64, isize);
+into_cell!(UInt(u64), Align::Right, u8, u16, u32, u64, usize);
+into_cell!(Float(f64), Align::Right, f32, f64);
+
+impl IntoCell for bool {
+    fn into_cell(self) -> Cell {
+        Cell::Bool(self)
+    }
+    fn align() -> Align {
+        Align::Center
+    }
+}
+
+impl IntoCell for Duration {
+    fn into_cell(self) -> Cell {
+        Cell::Duration(self)
+    }
+    fn align() -> Align {
+        Align::Right
+    }
+}
+
+impl IntoCell for String {
+    fn into_cell(self) -> Cell {
+        Cell::Text(self)
+    }
+}
+
+impl IntoCell for &str {
+    fn into_cell(self) -> Cell {
+        Cell::Text(self.to_owned())
+    }
+}
+
+impl IntoCell for &String {
+    fn into_cell(self) -> Cell {
+        Cell::Text(self.clone())
+    }
+}
+
+impl IntoCell for Cow<'_, str> {
+    fn into_cell(self) -> Cell {
+        Cell::Text(self.into_owned())
+    }
+}
+
+impl IntoCell for char {
+    fn into_cell(self) -> Cell {
+        Cell::Text(self.to_string())
+    }
+}
+
+impl<V: IntoCell> IntoCell for Option<V> {
+    fn into_cell(self) -> Cell {
+        self.map_or(Cell::Null, IntoCell::into_cell)
+    }
+    fn align() -> Align {
+        V::align()
+    }
+}
+
+impl CellFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets number of fractional digits of floats, durations and numbers
+    /// shortened with units. By default at most 2 digits are shown.
+    pub fn precision(mut self, digits: usize) -> Self {
+        self.precision = Some(digits);
+        self
+    }
+    /// Groups digits of integer part of numbers by thousands.
+    pub fn thousands(mut self, separator: char) -> Self {
+        self.thousands = Some(separator);
+        self
+    }
+    /// Shortens numbers with multiples of units. Sizes in bytes use IEC
+    /// units unless set otherwise.
+    pub fn units(mut self, units: Units) -> Self {
+        self.units = Some(units);
+        self
+    }
+
+    pub(crate) fn format(&self, cell: &Cell, null: &str) -> String {
+        match cell {
+            Cell::Text(s) => s.clone(),
+            Cell::Bool(b) => b.to_string(),
+            Cell::Null => null.to_owned(),
+            Cell::Duration(d) => self.duration(*d),
+            Cell::Bytes(n) => self.scaled(*n as f64, self.units.unwrap_or(Units::Iec), "B"),
+            Cell::Int(n) => self.number(*n as f64, n.to_string()),
+            Cell::UInt(n) => self.number(*n as f64, n.to_string()),
+            Cell::Float(x) => self.number(*x, self.decimal(*x)),
+        }
+    }
+
+    fn number(&self, value: f64, exact: String) -> String {
+        match self.units {
+            Some(units) => self.scaled(value, units, ""),
+            None => self.group(exact),
+        }
+    }
+
+    fn scaled(&self, mut value: f64, units: Units, unit: &str) -> String {
+        let (base, prefixes) = match units {
+            Units::Si => (1000.0, ["", "k", "M", "G", "T", "P", "E"]),
+            Units::Iec => (1024.0, ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei"]),
+        };
+        let mut idx = 0;
+        while value.abs() >= base && idx < prefixes.len() - 1 {
+            value /= base;
+            idx += 1;
+        }
+        let number = if idx == 0 && value.fract() == 0.0 {
+            self.group(format!("{value}"))
+        } else {
+            self.group(self.decimal(value))
+        };
+        let sep = if unit.is_empty() { "" } else { " " };
+        format!("{number}{sep}{}{unit}", prefixes[idx])
+    }
+
+    fn duration(&self, d: Duration) -> String {
+        let secs = d.as_secs();
+        if secs >= 3600 {
+            return format!("{}h {}m {}s", secs / 3600, secs / 60 % 60, secs % 60);
+        }
+        if secs >= 60 {
+            return format!("{}m {}s", secs / 60, secs % 60);
+        }
+        let nanos = d.as_nanos() as f64;
+        let (value, unit) = match nanos {
+            n if n >= 1e9 => (n / 1e9, "s"),
+            n if n >= 1e6 => (n / 1e6, "ms"),
+            n if n >= 1e3 => (n / 1e3, "µs"),
+            n => (n, "ns"),
+        };
+        format!("{}{unit}", self.decimal(value))
+    }
+
+    fn decimal(&self, value: f64) -> String {
+        match self.precision {
+            Some(p) => format!("{value:.p$}"),
+            None => {
+                let s = format!("{value:.2}");
+                s.trim_end_matches('0').trim_end_matches('.').to_owned()
+            }
+        }
+    }
+
+    /// Inserts thousands separators into integer part of given number.
+    fn group(&self, number: String) -> String {
+        let Some(sep) = self.thousands else {
+            return number;
+        };
+        let start = number.find(|c: char| c.is_ascii_digit()).unwrap_or(0);
+        let end = number[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(number.len(), |len| start + len);
+        let digits = &number[start..end];
+
+        // leading group takes what is left over from full groups of 3.
+        let head = digits.len() % 3;
+        let mut grouped = format!("{}{}", &number[..start], &digits[..head]);
+        for at in (head..digits.len()).step_by(3) {
+            if at > 0 {
+                grouped.push(sep);
+            }
+            grouped.push_str(&digits[at..at + 3]);
+        }
+        grouped.push_str(&number[end..]);
+        grouped
+    }
+}
+
+impl<'a, T: AsRef<str>> FancyTable<'a, T> {
+    /// Turns typed rows into text, formatting each cell with formatter
+    /// of its column.
+    pub fn format_rows<R, C>(&self, rows: impl IntoIterator<Item = R>) -> Vec<Vec<String>>
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoCell,
+    {
+        rows.into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .enumerate()
+                    .map(|(i, cell)| {
+                        let format = self.columns.get(i).map(|c| c.format).unwrap_or_default();
+                        format.format(&cell.into_cell(), self.null_marker)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Renders table with given typed rows to standard output.
+    pub fn render_cells<R, C>(&self, rows: impl IntoIterator<Item = R>) -> io::Result<()>
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoCell,
+    {
+        self.render_cells_to(&mut io::stdout().lock(), rows)
+    }
+
+    /// Renders table with given typed rows to any [`io::Write`] sink.
+    pub fn render_cells_to<W: io::Write, R, C>(
+        &self,
+        w: &mut W,
+        rows: impl IntoIterator<Item = R>,
+    ) -> io::Result<()>
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoCell,
+    {
+        self.write_table_io(w, &self.format_rows(rows))
+    }
+
+    /// Renders table with given typed rows into a String.
+    pub fn render_cells_to_string<R, C>(&self, rows: impl IntoIterator<Item = R>) -> String
+    where
+        R: IntoIterator<Item = C>,
+        C: IntoCell,
+    {
+        export(|w| self.write_table(w, &self.format_rows(rows)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{FancyTableOpts, Layout};
+
+    #[test]
+    fn format_numbers() {
+        let format = CellFormat::new().thousands(',');
+        assert_eq!(format.format(&(-1234567).into_cell(), ""), "-1,234,567");
+        assert_eq!(format.format(&1234.5.into_cell(), ""), "1,234.5");
+        assert_eq!(format.format(&(0.1 + 0.2).into_cell(), ""), "0.3");
+        assert_eq!(format.format(&123456.into_cell(), ""), "123,456");
+
+        let format = format.precision(2);
+        assert_eq!(format.format(&1234.5.into_cell(), ""), "1,234.50");
+        assert_eq!(format.format(&255u8.into_cell(), ""), "255");
+        assert_eq!(format.format(&None::<u8>.into_cell(), "-"), "-");
+    }
+
+    #[test]
+    fn format_units_and_durations() {
+        let format = CellFormat::new();
+        assert_eq!(format.format(&Cell::bytes(512), ""), "512 B");
+        assert_eq!(format.format(&Cell::bytes(1536), ""), "1.5 KiB");
+        assert_eq!(
+            format.units(Units::Si).format(&Cell::bytes(1536), ""),
+            "1.54 kB"
+        );
+        assert_eq!(
+            format.units(Units::Si).format(&2_500_000.into_cell(), ""),
+            "2.5M"
+        );
+        let ms = |n| Duration::from_millis(n).into_cell();
+        assert_eq!(format.format(&ms(250), ""), "250ms");
+        assert_eq!(format.format(&ms(1500), ""), "1.5s");
+        assert_eq!(format.format(&ms(3_725_000), ""), "1h 2m 5s");
+        assert_eq!(format.precision(1).format(&ms(1), ""), "1.0ms");
+    }
+
+    #[test]
+    fn render_typed_rows() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("NAME", Layout::Fixed(8))
+            .add_column_typed::<u64>("SIZE", Layout::Fixed(10))
+            .add_column_typed::<Option<bool>>("OK", Layout::Fixed(7))
+            .column_format(1, CellFormat::new().thousands(' '))
+            .null_marker("?")
+            .build(0);
+
+        let rows = vec![
+            vec![Cell::display("ring"), 1.into_cell(), Some(true).into_cell()],
+            vec![
+                Cell::display("cloak"),
+                123456.into_cell(),
+                None::<bool>.into_cell(),
+            ],
+        ];
+        assert_eq!(
+            table.render_csv(table.format_rows(rows.clone())),
+            "NAME,SIZE,OK\r\nring,1,true\r\ncloak,123 456,?\r\n"
+        );
+        assert_eq!(
+            table.render_cells_to_string(rows),
+            "\
+╭────────┬──────────┬───────╮
+│ NAME   │     SIZE │  OK   │
+╞════════╪══════════╪═══════╡
+│ ring   │        1 │ true  │
+│ cloak  │  123 456 │   ?   │
+╰────────┴──────────┴───────╯
+"
+        );
+    }
+}
@@ -15,9 +15,11 @@ pub struct TableChars {
     pub dnws: char,
     pub dnews: char,
     pub title: char,
+    pub ellipsis: &'static str,
 }
 
 #[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Charset {
     Classic,
     Modern,
@@ -45,6 +47,7 @@ impl Charset {
                 dnws: '╡',
                 dnews: '╪',
                 title: '▪',
+                ellipsis: "…",
             },
             Self::Classic => TableChars {
                 se: '┌',
@@ -63,6 +66,7 @@ impl Charset {
                 dnws: '╡',
                 dnews: '╪',
                 title: '▪',
+                ellipsis: "…",
             },
             Self::Simple => TableChars {
                 se: '+',
@@ -81,6 +85,7 @@ impl Charset {
                 dnws: '|',
                 dnews: '=',
                 title: '*',
+                ellipsis: "...",
             },
             Self::Minimal => TableChars {
                 se: ' ',
@@ -99,6 +104,7 @@ impl Charset {
                 dnws: ' ',
                 dnews: '=',
                 title: '=',
+                ellipsis: "...",
             },
         }
     }
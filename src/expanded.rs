@@ -0,0 +1,243 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    fancy::{collect, decimal_aligned},
+    padstr::{display_width, Pad, PadStr},
+    FancyTable,
+};
+
+impl<'a, T: AsRef<str>> FancyTable<'a, T> {
+    /// Renders each row as a separate block of `HEADER │ value` lines,
+    /// preceded by a separator with record number.
+    pub(crate) fn render_expanded<W: Write, S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        w: &mut W,
+        rows: &[R],
+    ) -> fmt::Result {
+        let ch = &self.chars;
+        let border = &self.styles.border;
+        let headers = self.header_row();
+        let decimals = self.decimal_widths(rows);
+        let padding = self.padding;
+        let key_width = headers.iter().map(|h| display_width(h)).max().unwrap_or(0) + 2 * padding;
+
+        // table fit to its content gets as wide as the widest value. leave
+        // at least a single character for values, no matter how narrow
+        // requested table is.
+        let width = match self.fit_width {
+            true => {
+                let values = rows
+                    .iter()
+                    .flat_map(|row| row.as_ref().iter())
+                    .flat_map(|cell| cell.as_ref().lines())
+                    .map(display_width);
+                let decimals = decimals.iter().flatten().map(|(int, frac)| int + frac);
+                let val_width = values.chain(decimals).max().unwrap_or(0);
+                key_width + val_width + 2 * padding + 3
+            }
+            false => self.width,
+        }
+        .max(key_width + 2 * padding + 4);
+        let val_width = width - key_width - 3;
+        let joint = key_width + 1;
+
+        let line = |first: char, mid: char, last: char| {
+            let mut chars = vec![ch.ew; width];
+            chars[0] = first;
+            chars[joint] = mid;
+            chars[width - 1] = last;
+            chars
+        };
+
+        writeln!(w, "{}", self.top_border(&line(ch.se, ch.ews, ch.sw)))?;
+
+        for (i, row) in rows.iter().enumerate() {
+            let mut separator = line(ch.nes, ch.news, ch.nws);
+            // record label goes into key segment, or value segment when
+            // keys are too narrow, never covering the joint.
+            let label = format!("[ RECORD {} ]", i + 1);
+            let start = match 2 + label.len() <= joint {
+                true => Some(2),
+                false => (joint + 2 + label.len() < width).then_some(joint + 2),
+            };
+            if let Some(start) = start {
+                separator.splice(start..start + label.len(), label.chars());
+            }
+            writeln!(w, "{}", self.paint(border, &collect(&separator)))?;
+
+            for (j, cell) in row.as_ref().iter().enumerate() {
+                let Some(col) = self.columns.get(j) else {
+                    continue;
+                };
+                let key =
+                    PadStr::truncating(headers[j]).paddify(key_width - 2 * padding, 1, Pad::Right);
+                // decimal aligned values get lined up across records
+                let value =
+                    decimal_aligned(col, cell.as_ref(), decimals[j], val_width - 2 * padding);
+                let values = self.padstr(col, &value).paddify(
+                    val_width - 2 * padding,
+                    col.max_lines,
+                    Pad::Right,
+                );
+                let empty_key = " ".repeat(key_width - 2 * padding);
+                let empty_val = " ".repeat(val_width - 2 * padding);
+                let lines = values.len().max(1);
+
+                for n in 0..lines {
+                    let k = key.get(n).unwrap_or(&empty_key);
+                    let v = values.get(n).unwrap_or(&empty_val);
+                    self.write_record_line(
+                        w,
+                        &self.paint(&self.cell_style(None, j), k),
+                        &self.paint(&self.cell_style(Some(i), j), v),
+                    )?;
+                }
+            }
+        }
+        writeln!(
+            w,
+            "{}",
+            self.paint(border, &collect(&line(ch.ne, ch.new, ch.nw)))
+        )
+    }
+
+    fn write_record_line<W: Write>(&self, w: &mut W, key: &str, value: &str) -> fmt::Result {
+        let ns = self
+            .paint(&self.styles.border, &self.chars.ns.to_string())
+            .into_owned();
+        let pad = self.padding;
+
+        writeln!(
+            w,
+            "{ns}{:pad$}{key}{:pad$}{ns}{:pad$}{value}{:pad$}{ns}",
+            "", "", "", ""
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        padstr::display_width, Align, Expanded, FancyTable, FancyTableOpts, Layout, TitleAlign,
+    };
+
+    #[test]
+    fn expanded_records() {
+        let table = FancyTable::create(FancyTableOpts {
+            expanded: Expanded::Always,
+            ..Default::default()
+        })
+        .add_column_named("ID", Layout::Slim)
+        .add_column_named_wrapping("DESCRIPTION", Layout::Expandable(40))
+        .add_title_with_align("elves", TitleAlign::RightOffset(1))
+        .build(30);
+
+        let output = table.render_to_string([
+            ["1", "Maeglin is an elf who betrayed his fellow elves"],
+            ["29", "Tauriel"],
+        ]);
+        assert_eq!(
+            output,
+            "\
+╭─────────────┬────▪ elves ▪─╮
+├─[ RECORD 1 ]┼──────────────┤
+│ ID          │ 1            │
+│ DESCRIPTION │ Maeglin is   │
+│             │ an elf who   │
+│             │ betrayed his │
+├─[ RECORD 2 ]┼──────────────┤
+│ ID          │ 29           │
+│ DESCRIPTION │ Tauriel      │
+╰─────────────┴──────────────╯
+"
+        );
+    }
+
+    #[test]
+    fn expanded_record_label_keeps_joint() {
+        let table = FancyTable::create(FancyTableOpts {
+            expanded: Expanded::Always,
+            ..Default::default()
+        })
+        .add_column_named("ID", Layout::Slim)
+        .add_column_named("NM", Layout::Slim)
+        .build(24);
+
+        assert_eq!(
+            table.render_to_string([["1", "Frodo"]]),
+            "\
+╭────┬─────────────────╮
+├────┼─[ RECORD 1 ]────┤
+│ ID │ 1               │
+│ NM │ Frodo           │
+╰────┴─────────────────╯
+"
+        );
+    }
+
+    #[test]
+    fn expanded_fit_to_content() {
+        let table = FancyTable::create(FancyTableOpts {
+            expanded: Expanded::Always,
+            ..Default::default()
+        })
+        .add_column_named("ID", Layout::Slim)
+        .add_column_named("NAME", Layout::Slim)
+        .build_fit_content();
+
+        assert_eq!(
+            table.render_to_string([["1", "Frodo Baggins"]]),
+            "\
+╭──────┬───────────────╮
+├──────┼─[ RECORD 1 ]──┤
+│ ID   │ 1             │
+│ NAME │ Frodo Baggins │
+╰──────┴───────────────╯
+"
+        );
+    }
+
+    #[test]
+    fn expanded_automatically_when_too_narrow() {
+        let build = |width| {
+            FancyTable::create(FancyTableOpts {
+                expanded: Expanded::Auto,
+                ..Default::default()
+            })
+            .add_column_named("ID", Layout::Fixed(10))
+            .add_column_named("NAME", Layout::Fixed(10))
+            .build(width)
+        };
+
+        let wide = build(23).render_to_string([["1", "Frodo"]]);
+        assert!(!wide.contains("RECORD"));
+
+        let narrow = build(20).render_to_string([["1", "Frodo"]]);
+        assert!(narrow.contains("│ NAME │ Frodo"));
+        for line in narrow.lines() {
+            assert_eq!(display_width(line), 20);
+        }
+    }
+
+    #[test]
+    fn expanded_decimal_aligned_values() {
+        let table = FancyTable::create(FancyTableOpts {
+            expanded: Expanded::Always,
+            ..Default::default()
+        })
+        .add_column_named_with_align("PRICE", Layout::Slim, Align::Decimal('.'))
+        .build(26);
+
+        assert_eq!(
+            table.render_to_string([["1024.5"], ["-3.125"]]),
+            "\
+╭───────┬────────────────╮
+├───────┼─[ RECORD 1 ]───┤
+│ PRICE │ 1024.5         │
+├───────┼─[ RECORD 2 ]───┤
+│ PRICE │   -3.125       │
+╰───────┴────────────────╯
+"
+        );
+    }
+}
@@ -0,0 +1,426 @@
+use std::fmt::{self, Write};
+
+use crate::{ansi, charset::Charset, Align, FancyTable, Separator, TitleAlign};
+
+impl<'a, T: AsRef<str>> FancyTable<'a, T> {
+    /// Renders table as GitHub Flavored Markdown.
+    pub fn render_markdown<S: AsRef<str>, R: AsRef<[S]>>(&self, rows: impl AsRef<[R]>) -> String {
+        export(|w| self.write_markdown(w, rows.as_ref(), false))
+    }
+
+    /// Renders table as GitHub Flavored Markdown with a title emitted
+    /// as a heading above the table.
+    pub fn render_markdown_with_title<S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        rows: impl AsRef<[R]>,
+    ) -> String {
+        export(|w| self.write_markdown(w, rows.as_ref(), true))
+    }
+
+    fn write_markdown<W: Write, S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        w: &mut W,
+        rows: &[R],
+        with_title: bool,
+    ) -> fmt::Result {
+        if let Some(spec) = self.title.as_ref().filter(|_| with_title) {
+            writeln!(w, "## {}\n", markdown_escape(spec.title))?;
+        }
+        write_markdown_row(w, &self.header_row())?;
+
+        let delimiters = self
+            .columns
+            .iter()
+            .map(|c| match c.align {
+                Align::Left => ":--",
+                Align::Center => ":-:",
+                Align::Right | Align::Decimal(_) => "--:",
+            })
+            .collect::<Vec<_>>();
+        writeln!(w, "|{}|", delimiters.join("|"))?;
+
+        for row in rows {
+            write_markdown_row(w, row.as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T: AsRef<str>> FancyTable<'a, T> {
+    /// Renders table as a standalone HTML `<table>`.
+    pub fn render_html<S: AsRef<str>, R: AsRef<[S]>>(&self, rows: impl AsRef<[R]>) -> String {
+        export(|w| self.write_html(w, rows.as_ref()))
+    }
+
+    /// Renders table as HTML preceded by a `<style>` block mimicking
+    /// table charset and separators.
+    pub fn render_html_with_theme<S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        rows: impl AsRef<[R]>,
+    ) -> String {
+        export(|w| {
+            self.write_html_theme(w)?;
+            self.write_html(w, rows.as_ref())
+        })
+    }
+
+    fn write_html<W: Write, S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        w: &mut W,
+        rows: &[R],
+    ) -> fmt::Result {
+        writeln!(w, "<table class=\"fancy-table\">")?;
+        if let Some(spec) = &self.title {
+            writeln!(w, "<caption>{}</caption>", html_escape(spec.title))?;
+        }
+        if self.has_headers() {
+            writeln!(w, "<thead>")?;
+            self.write_html_row(w, "th", &self.header_row())?;
+            writeln!(w, "</thead>")?;
+        }
+        writeln!(w, "<tbody>")?;
+        for row in rows {
+            self.write_html_row(w, "td", row.as_ref())?;
+        }
+        writeln!(w, "</tbody>")?;
+        writeln!(w, "</table>")
+    }
+
+    fn write_html_row<W: Write, S: AsRef<str>>(
+        &self,
+        w: &mut W,
+        tag: &str,
+        row: &[S],
+    ) -> fmt::Result {
+        write!(w, "<tr>")?;
+        for (cell, col) in row.iter().zip(&self.columns) {
+            let align = match col.align {
+                Align::Left => "left",
+                Align::Center => "center",
+                Align::Right | Align::Decimal(_) => "right",
+            };
+            write!(
+                w,
+                "<{tag} style=\"text-align: {align}\">{}</{tag}>",
+                html_escape(cell.as_ref())
+            )?;
+        }
+        writeln!(w, "</tr>")
+    }
+
+    fn write_html_theme<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let (border, radius) = match self.charset {
+            Charset::Modern => ("1px solid", "6px"),
+            Charset::Classic => ("1px solid", "0"),
+            Charset::Simple => ("1px dashed", "0"),
+            Charset::Minimal => ("none", "0"),
+        };
+        let caption = match self.title.as_ref().map(|t| &t.align) {
+            Some(TitleAlign::RightOffset(_)) => "right",
+            _ => "left",
+        };
+        let hsep = separator_css(&self.headers_separator);
+        let rsep = separator_css(&self.rows_separator);
+        let padding = self.padding;
+
+        writeln!(w, "<style>")?;
+        writeln!(
+            w,
+            ".fancy-table {{ border: {border}; border-radius: {radius}; border-spacing: 0; font-family: monospace; }}"
+        )?;
+        writeln!(w, ".fancy-table caption {{ text-align: {caption}; }}")?;
+        writeln!(
+            w,
+            ".fancy-table th, .fancy-table td {{ padding: 0 {padding}ch; vertical-align: top; }}"
+        )?;
+        writeln!(
+            w,
+            ".fancy-table th + th, .fancy-table td + td {{ border-left: {border}; }}"
+        )?;
+        writeln!(w, ".fancy-table thead th {{ border-bottom: {hsep}; }}")?;
+        writeln!(w, ".fancy-table tbody tr + tr td {{ border-top: {rsep}; }}")?;
+        writeln!(w, "</style>")
+    }
+}
+
+impl<'a, T: AsRef<str>> FancyTable<'a, T> {
+    /// Renders headers and rows as RFC 4180 CSV, ignoring layout,
+    /// truncation and wrapping.
+    pub fn render_csv<S: AsRef<str>, R: AsRef<[S]>>(&self, rows: impl AsRef<[R]>) -> String {
+        export(|w| self.write_delimited(w, rows.as_ref(), ',', "\r\n"))
+    }
+
+    /// Renders headers and rows as tab separated values, quoted the same
+    /// way as CSV.
+    pub fn render_tsv<S: AsRef<str>, R: AsRef<[S]>>(&self, rows: impl AsRef<[R]>) -> String {
+        export(|w| self.write_delimited(w, rows.as_ref(), '\t', "\n"))
+    }
+
+    fn write_delimited<W: Write, S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        w: &mut W,
+        rows: &[R],
+        delimiter: char,
+        eol: &str,
+    ) -> fmt::Result {
+        if self.has_headers() {
+            write_delimited_row(w, &self.header_row(), delimiter, eol)?;
+        }
+        for row in rows {
+            write_delimited_row(w, row.as_ref(), delimiter, eol)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'a, T: AsRef<str>> FancyTable<'a, T> {
+    /// Renders rows as a JSON array of objects keyed by column headers.
+    /// Columns without a header are keyed by their position.
+    pub fn render_json<S: AsRef<str>, R: AsRef<[S]>>(&self, rows: impl AsRef<[R]>) -> String {
+        export(|w| {
+            let rows = rows.as_ref();
+            writeln!(w, "[")?;
+            for (i, row) in rows.iter().enumerate() {
+                write!(w, "  ")?;
+                self.write_json_object(w, row.as_ref())?;
+                writeln!(w, "{}", if i < rows.len() - 1 { "," } else { "" })?;
+            }
+            writeln!(w, "]")
+        })
+    }
+
+    /// Renders rows as JSON Lines, one object per row.
+    pub fn render_jsonl<S: AsRef<str>, R: AsRef<[S]>>(&self, rows: impl AsRef<[R]>) -> String {
+        export(|w| {
+            for row in rows.as_ref() {
+                self.write_json_object(w, row.as_ref())?;
+                writeln!(w)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn write_json_object<W: Write, S: AsRef<str>>(&self, w: &mut W, row: &[S]) -> fmt::Result {
+        write!(w, "{{")?;
+        for (i, cell) in row.iter().enumerate() {
+            if i > 0 {
+                write!(w, ",")?;
+            }
+            match self.headers.get(i).and_then(Option::as_ref) {
+                Some(header) => write_json_string(w, header.as_ref())?,
+                None => write_json_string(w, &i.to_string())?,
+            }
+            write!(w, ":")?;
+            write_json_string(w, cell.as_ref())?;
+        }
+        write!(w, "}}")
+    }
+}
+
+pub(crate) fn export<F: FnOnce(&mut String) -> fmt::Result>(f: F) -> String {
+    let mut out = String::new();
+    f(&mut out).expect("writing to a String should never fail");
+    out
+}
+
+fn write_markdown_row<W: Write, S: AsRef<str>>(w: &mut W, row: &[S]) -> fmt::Result {
+    for cell in row {
+        write!(w, "| {} ", markdown_escape(cell.as_ref()))?;
+    }
+    writeln!(w, "|")
+}
+
+fn markdown_escape(s: &str) -> String {
+    ansi::strip(s)
+        .lines()
+        .map(|l| l.replace('|', "\\|"))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+fn write_delimited_row<W: Write, S: AsRef<str>>(
+    w: &mut W,
+    row: &[S],
+    delimiter: char,
+    eol: &str,
+) -> fmt::Result {
+    for (i, cell) in row.iter().enumerate() {
+        if i > 0 {
+            w.write_char(delimiter)?;
+        }
+        let cell = ansi::strip(cell.as_ref());
+        if cell.contains([delimiter, '"', '\r', '\n']) {
+            write!(w, "\"{}\"", cell.replace('"', "\"\""))?;
+        } else {
+            w.write_str(&cell)?;
+        }
+    }
+    w.write_str(eol)
+}
+
+#[cfg(feature = "json")]
+fn write_json_string<W: Write>(w: &mut W, s: &str) -> fmt::Result {
+    w.write_char('"')?;
+    for c in ansi::strip(s).chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            c if c < ' ' => write!(w, "\\u{:04x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
+
+fn html_escape(s: &str) -> String {
+    ansi::strip(s)
+        .lines()
+        .map(|l| {
+            l.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('"', "&quot;")
+                .replace('\'', "&#39;")
+        })
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+fn separator_css(separator: &Option<Separator>) -> &'static str {
+    match separator {
+        Some(Separator::Single) => "1px solid",
+        Some(Separator::Double) => "3px double",
+        Some(Separator::Custom(_)) => "1px dashed",
+        None => "none",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{charset::Charset, Align, FancyTable, FancyTableOpts, Layout, Overflow};
+
+    #[test]
+    fn markdown_table() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Slim)
+            .add_column_named_with_align("NAME", Layout::Fixed(4), Align::Center)
+            .add_column(None, Layout::Fixed(10), Align::Right, Overflow::Wrap, 2)
+            .add_title("Fellowship | Ring")
+            .build(30);
+
+        let rows = [
+            ["1", "Frodo", "ring\nbearer"],
+            ["2", "\x1b[1mSam\x1b[0m", "a|b"],
+        ];
+        assert_eq!(
+            table.render_markdown(rows),
+            "\
+| ID | NAME |  |
+|:--|:-:|--:|
+| 1 | Frodo | ring<br>bearer |
+| 2 | Sam | a\\|b |
+"
+        );
+        assert!(table
+            .render_markdown_with_title(rows)
+            .starts_with("## Fellowship \\| Ring\n\n| ID |"));
+    }
+
+    #[test]
+    fn html_table() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Slim)
+            .add_column_named_with_align("NAME", Layout::Fixed(4), Align::Right)
+            .add_title("<props>")
+            .build(30);
+
+        assert_eq!(
+            table.render_html([["1", "Frodo & \"Sam\"\nBaggins"]]),
+            "\
+<table class=\"fancy-table\">
+<caption>&lt;props&gt;</caption>
+<thead>
+<tr><th style=\"text-align: left\">ID</th><th style=\"text-align: right\">NAME</th></tr>
+</thead>
+<tbody>
+<tr><td style=\"text-align: left\">1</td><td style=\"text-align: right\">Frodo &amp; &quot;Sam&quot;<br>Baggins</td></tr>
+</tbody>
+</table>
+"
+        );
+    }
+
+    #[test]
+    fn html_table_with_theme() {
+        let table = FancyTable::<&str>::create(FancyTableOpts {
+            charset: Charset::Minimal,
+            ..Default::default()
+        })
+        .add_column(None, Layout::Fixed(4), Align::Left, Overflow::Truncate, 1)
+        .build(10);
+
+        let html = table.render_html_with_theme([["1"]]);
+        assert!(html.starts_with("<style>\n.fancy-table { border: none;"));
+        assert!(html.contains(".fancy-table thead th { border-bottom: 3px double; }"));
+        assert!(!html.contains("<thead>"));
+    }
+
+    #[test]
+    fn csv_and_tsv() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Slim)
+            .add_column_named_wrapping("NAME, FULL", Layout::Fixed(4))
+            .add_column(None, Layout::Fixed(4), Align::Left, Overflow::Truncate, 1)
+            .build(30);
+
+        let rows = [
+            ["1", "Frodo \"Ring-bearer\"\nBaggins", "hobbit"],
+            ["2", "Sam\tGamgee", "\x1b[32mgardener\x1b[0m"],
+        ];
+        assert_eq!(
+            table.render_csv(rows),
+            "ID,\"NAME, FULL\",\r\n\
+             1,\"Frodo \"\"Ring-bearer\"\"\nBaggins\",hobbit\r\n\
+             2,Sam\tGamgee,gardener\r\n"
+        );
+        assert_eq!(
+            table.render_tsv(rows),
+            "ID\tNAME, FULL\t\n\
+             1\t\"Frodo \"\"Ring-bearer\"\"\nBaggins\"\thobbit\n\
+             2\t\"Sam\tGamgee\"\tgardener\n"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_and_jsonl() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Slim)
+            .add_column(None, Layout::Fixed(4), Align::Left, Overflow::Truncate, 1)
+            .add_column_named("NOTE", Layout::Fixed(4))
+            .build(30);
+
+        let rows = [
+            ["1", "Frodo", "says \"hi\"\n\\o/"],
+            ["2", "\x1b[1mSam\x1b[0m", "\u{1}"],
+        ];
+        assert_eq!(
+            table.render_json(rows),
+            r#"[
+  {"ID":"1","1":"Frodo","NOTE":"says \"hi\"\n\\o/"},
+  {"ID":"2","1":"Sam","NOTE":"\u0001"}
+]
+"#
+        );
+        assert_eq!(
+            table.render_jsonl(rows),
+            r#"{"ID":"1","1":"Frodo","NOTE":"says \"hi\"\n\\o/"}
+{"ID":"2","1":"Sam","NOTE":"\u0001"}
+"#
+        );
+    }
+}
@@ -1,8 +1,14 @@
+use std::{borrow::Cow, cmp::Reverse, collections::HashMap, fmt, io};
+
+#[cfg(feature = "hyphenation")]
+use crate::Language;
 use crate::{
+    cell::{CellFormat, IntoCell},
     charset::Charset,
-    padstr::{Pad, PadStr},
-    Align, ColSpec, FancyTable, FancyTableBuilder, FancyTableOpts, Layout, Overflow, Separator,
-    TitleAlign, TitleSpec,
+    padstr::{align_decimal, decimal_parts, display_width, Breakpoints, Elide, More, Pad, PadStr},
+    style::{ColorMode, Style},
+    term, Align, ColSpec, Expanded, FancyTable, FancyTableBuilder, FancyTableOpts, Layout,
+    LineBreaking, MoreLines, Overflow, Separator, TableStyles, TitleAlign, TitleSpec, VAlign,
 };
 
 const DEFAULT_COLUMN_WIDTH: usize = 10;
@@ -15,6 +21,11 @@ impl Default for FancyTableOpts {
             headers_separator: Some(Separator::Double),
             rows_separator: None,
             max_lines: 3,
+            more_lines: MoreLines::Hidden,
+            color: ColorMode::Never,
+            expanded: Expanded::Never,
+            fit_content: false,
+            fit_when_piped: false,
         }
     }
 }
@@ -31,6 +42,17 @@ impl<'a, T: AsRef<str>> FancyTableBuilder<'a, T> {
             max_lines: opts.max_lines,
             title: None,
             title_align: opts.title_align,
+            color: opts.color,
+            expanded: opts.expanded,
+            fit_content: opts.fit_content,
+            fit_when_piped: opts.fit_when_piped,
+            hidden_note: false,
+            more_lines: opts.more_lines,
+            continuation: None,
+            word_breaks: "",
+            row_valigns: HashMap::new(),
+            null_marker: "",
+            styles: TableStyles::default(),
         }
     }
     fn add_column_spec(
@@ -45,8 +67,18 @@ impl<'a, T: AsRef<str>> FancyTableBuilder<'a, T> {
             width,
             layout,
             align,
+            valign: VAlign::Top,
             overflow,
             max_lines,
+            line_breaking: LineBreaking::Greedy,
+            format: CellFormat::default(),
+            #[cfg(feature = "hyphenation")]
+            hyphenation: None,
+            style: Style::default(),
+            shrink: 0,
+            min_width: None,
+            priority: None,
+            hidden: false,
         });
         self
     }
@@ -63,12 +95,10 @@ impl<'a, T: AsRef<str>> FancyTableBuilder<'a, T> {
             Layout::Fixed(f) => f,
             _ => header
                 .as_ref()
-                .map(|h| h.as_ref().chars().count())
+                .map(|h| display_width(h.as_ref()))
                 .unwrap_or(DEFAULT_COLUMN_WIDTH),
         };
-        if let Some(header) = header {
-            self.headers.push(header);
-        }
+        self.headers.push(header);
         self.add_column_spec(len, max_lines, layout, align, overflow)
     }
     pub fn add_column_named(self, header: T, layout: Layout) -> Self {
@@ -78,10 +108,10 @@ impl<'a, T: AsRef<str>> FancyTableBuilder<'a, T> {
         self.add_column_named_wrapping_with_align(header, layout, Align::Left)
     }
     pub fn add_column_named_with_align(mut self, header: T, layout: Layout, align: Align) -> Self {
-        let len = header.as_ref().len();
+        let len = display_width(header.as_ref());
         let max_lines = self.max_lines;
 
-        self.headers.push(header);
+        self.headers.push(Some(header));
         self.add_column_spec(len, max_lines, layout, align, Overflow::Truncate)
     }
     pub fn add_column_named_wrapping_with_align(
@@ -90,12 +120,21 @@ impl<'a, T: AsRef<str>> FancyTableBuilder<'a, T> {
         layout: Layout,
         align: Align,
     ) -> Self {
-        let len = header.as_ref().len();
+        let len = display_width(header.as_ref());
         let max_lines = self.max_lines;
 
-        self.headers.push(header);
+        self.headers.push(Some(header));
         self.add_column_spec(len, max_lines, layout, align, Overflow::Wrap)
     }
+    /// Adds column aligned according to type `V` of its values, eg. numbers
+    /// get aligned right.
+    pub fn add_column_typed<V: IntoCell>(self, header: T, layout: Layout) -> Self {
+        self.add_column_named_with_align(header, layout, V::align())
+    }
+    /// Number of columns added so far.
+    pub fn column_count(&self) -> usize {
+        self.columns.len()
+    }
     pub fn add_title(mut self, title: &'a str) -> Self {
         self.title = Some(title);
         self
@@ -116,89 +155,456 @@ impl<'a, T: AsRef<str>> FancyTableBuilder<'a, T> {
         self.rows_separator = separator;
         self
     }
+    pub fn color(mut self, mode: ColorMode) -> Self {
+        self.color = mode;
+        self
+    }
+    pub fn expanded(mut self, mode: Expanded) -> Self {
+        self.expanded = mode;
+        self
+    }
+    /// Sizes slim and expandable columns by the widest content in
+    /// rendered rows, not only by their headers.
+    pub fn fit_content(mut self, fit: bool) -> Self {
+        self.fit_content = fit;
+        self
+    }
+    /// Sets indicator of cells content cut at `max_lines`.
+    pub fn more_lines(mut self, more: MoreLines) -> Self {
+        self.more_lines = more;
+        self
+    }
+    /// Sets marker starting continuation lines of indentation-preserving
+    /// wrapped cells, eg. `↪`.
+    pub fn continuation(mut self, marker: &'a str) -> Self {
+        self.continuation = Some(marker);
+        self
+    }
+    /// Sets characters preferred to break words too long to fit into
+    /// wrapped columns after, eg. `"/-_."` for URLs and paths. By default
+    /// such words are broken wherever the line ends.
+    pub fn word_breaks(mut self, chars: &'a str) -> Self {
+        self.word_breaks = chars;
+        self
+    }
+    /// Sets style of all data cells in column `col` (0-based).
+    pub fn column_style(mut self, col: usize, style: Style) -> Self {
+        if let Some(spec) = self.columns.get_mut(col) {
+            spec.style = style;
+        }
+        self
+    }
+    /// Sets formatter of typed cells in column `col`.
+    pub fn column_format(mut self, col: usize, format: CellFormat) -> Self {
+        if let Some(spec) = self.columns.get_mut(col) {
+            spec.format = format;
+        }
+        self
+    }
+    /// Sets text of typed cells with no value (`None`), empty by default.
+    pub fn null_marker(mut self, marker: &'a str) -> Self {
+        self.null_marker = marker;
+        self
+    }
+    /// Sets vertical alignment of cells in column `col` against taller
+    /// cells in the same row.
+    pub fn column_valign(mut self, col: usize, valign: VAlign) -> Self {
+        if let Some(spec) = self.columns.get_mut(col) {
+            spec.valign = valign;
+        }
+        self
+    }
+    /// Sets vertical alignment of all cells in row `row` (0-based, headers
+    /// not counted in), overriding alignment of columns.
+    pub fn row_valign(mut self, row: usize, valign: VAlign) -> Self {
+        self.row_valigns.insert(row, valign);
+        self
+    }
+    /// Sets strategy of breaking text of wrapped column `col` into lines.
+    pub fn column_line_breaking(mut self, col: usize, breaking: LineBreaking) -> Self {
+        if let Some(spec) = self.columns.get_mut(col) {
+            spec.line_breaking = breaking;
+        }
+        self
+    }
+    /// Makes words of wrapped column `col` hyphenated with patterns of
+    /// given language, when they don't fit into a line.
+    #[cfg(feature = "hyphenation")]
+    pub fn column_hyphenation(mut self, col: usize, language: Language) -> Self {
+        if let Some(spec) = self.columns.get_mut(col) {
+            spec.hyphenation = Some(language);
+        }
+        self
+    }
+    /// Sets how eagerly column `col` gives up its width when table does
+    /// not fit into requested width. Columns shrink proportionally to their
+    /// weights, columns with weight 0 (default) never shrink.
+    pub fn column_shrink(mut self, col: usize, weight: usize) -> Self {
+        if let Some(spec) = self.columns.get_mut(col) {
+            spec.shrink = weight;
+        }
+        self
+    }
+    /// Sets width below which column `col` never shrinks. Defaults to
+    /// padding and a single character, which is also the lowest minimum.
+    pub fn column_min_width(mut self, col: usize, width: usize) -> Self {
+        if let Some(spec) = self.columns.get_mut(col) {
+            spec.min_width = Some(width);
+        }
+        self
+    }
+    /// Makes column `col` hideable. When table does not fit into requested
+    /// width even with all columns shrunk, hideable columns with lowest
+    /// priority get hidden first (rightmost ones when priorities are equal).
+    pub fn column_priority(mut self, col: usize, priority: u8) -> Self {
+        if let Some(spec) = self.columns.get_mut(col) {
+            spec.priority = Some(priority);
+        }
+        self
+    }
+    /// Shows number of hidden columns in the bottom border.
+    pub fn hidden_note(mut self, show: bool) -> Self {
+        self.hidden_note = show;
+        self
+    }
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.styles.header = style;
+        self
+    }
+    /// Sets style of row `row` (0-based, headers not counted in).
+    /// Row style is layered on top of column style.
+    pub fn row_style(mut self, row: usize, style: Style) -> Self {
+        self.styles.rows.insert(row, style);
+        self
+    }
+    /// Sets style of a single cell, layered on top of row and column styles.
+    pub fn cell_style(mut self, row: usize, col: usize, style: Style) -> Self {
+        self.styles.cells.insert((row, col), style);
+        self
+    }
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.styles.border = style;
+        self
+    }
+    pub fn title_style(mut self, style: Style) -> Self {
+        self.styles.title = style;
+        self
+    }
 
     pub fn build(self, table_width: usize) -> FancyTable<'a, T> {
+        self.build_with(table_width, false)
+    }
+
+    /// Builds a table exactly as wide as its content, measured when rendered.
+    pub fn build_fit_content(self) -> FancyTable<'a, T> {
+        self.build_with(0, true)
+    }
+
+    /// Builds a table as wide as terminal attached to stdout, falling back to
+    /// `COLUMNS` environment variable and eventually to 80 columns.
+    /// With `fit_when_piped` option enabled, table is fit to its content
+    /// when stdout is not a terminal.
+    pub fn build_for_terminal(self) -> FancyTable<'a, T> {
+        if self.fit_when_piped && !term::stdout_is_tty() {
+            return self.build_fit_content();
+        }
+        let width = term::terminal_width().unwrap_or(term::DEFAULT_TERMINAL_WIDTH);
+        self.build(width)
+    }
+
+    fn build_with(self, table_width: usize, fit_width: bool) -> FancyTable<'a, T> {
         let title = self.title.map(|t| TitleSpec {
             title: t,
             align: self.title_align,
         });
         let mut table = FancyTable {
             width: table_width,
+            min_width: 0,
+            fit_width,
             chars: self.charset.get_chars(),
+            charset: self.charset,
             rows_separator: self.rows_separator,
             headers_separator: self.headers_separator,
             padding: self.padding,
             headers: self.headers,
             columns: self.columns,
             title,
+            color: self.color,
+            expanded: self.expanded,
+            fit_content: self.fit_content,
+            hidden_note: self.hidden_note,
+            more_lines: self.more_lines,
+            continuation: self.continuation,
+            word_breaks: self.word_breaks,
+            row_valigns: self.row_valigns,
+            null_marker: self.null_marker,
+            styles: self.styles,
         };
         table.recalculate(table_width);
         table
     }
 }
 
+impl ColSpec {
+    #[cfg(feature = "hyphenation")]
+    fn breakpoints(&self) -> Option<Breakpoints> {
+        self.hyphenation.map(|language| language.breakpoints())
+    }
+    #[cfg(not(feature = "hyphenation"))]
+    fn breakpoints(&self) -> Option<Breakpoints> {
+        None
+    }
+}
+
 impl<'a, T: AsRef<str>> FancyTable<'a, T> {
     pub fn create(opts: FancyTableOpts) -> FancyTableBuilder<'a, T> {
         FancyTableBuilder::new(opts)
     }
 
     fn recalculate(&mut self, table_width: usize) {
-        let cols_count = self.columns.len();
+        let (widths, min_width) = self.layout(table_width, None);
+        for (spec, width) in self.columns.iter_mut().zip(widths) {
+            spec.width = width.unwrap_or(0);
+            spec.hidden = width.is_none();
+        }
+        self.min_width = min_width;
+    }
+
+    /// Calculates columns widths for given `table_width`, optionally
+    /// taking into account widths of the content to be rendered.
+    /// Returns widths (`None` for hidden columns) along with minimal
+    /// table width, ie. the one with all shrinkable columns shrunk to
+    /// their minimal widths.
+    fn layout(&self, table_width: usize, content: Option<&[usize]>) -> (Vec<Option<usize>>, usize) {
+        let mut hidden = vec![false; self.columns.len()];
+        loop {
+            let (widths, min_width) = self.layout_visible(table_width, content, &hidden);
+            let visible = hidden.iter().filter(|h| !**h).count();
+            let victim = self
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(i, c)| !hidden[*i] && c.priority.is_some())
+                .min_by_key(|(i, c)| (c.priority, Reverse(*i)))
+                .map(|(i, _)| i);
+
+            match victim {
+                Some(i) if min_width > table_width && visible > 1 => hidden[i] = true,
+                _ => {
+                    let widths = widths
+                        .into_iter()
+                        .zip(hidden)
+                        .map(|(w, h)| (!h).then_some(w))
+                        .collect();
+                    return (widths, min_width);
+                }
+            }
+        }
+    }
+
+    fn layout_visible(
+        &self,
+        table_width: usize,
+        content: Option<&[usize]>,
+        hidden: &[bool],
+    ) -> (Vec<usize>, usize) {
+        let cols_count = hidden.iter().filter(|h| !**h).count();
+        let mut widths = Vec::with_capacity(hidden.len());
+        let mut growable = Vec::new();
+        let mut shrinkable = Vec::new();
         let mut min_table_width = 0;
 
-        // calculate minimal table width with all paddings counted in
-        for (i, spec) in self.columns.iter_mut().enumerate() {
+        for (i, spec) in self.columns.iter().enumerate() {
+            if hidden[i] {
+                widths.push(0);
+                continue;
+            }
+            let header = self.header_width(i);
+            let content = content.and_then(|c| c.get(i)).copied();
+            // columns with neither header nor content get default width
+            let padded = |w: Option<usize>| {
+                w.map(|w| w + (2 * self.padding))
+                    .unwrap_or(DEFAULT_COLUMN_WIDTH)
+            };
+
             let column_width = match spec.layout {
                 Layout::Fixed(width) => width,
-                Layout::Slim | Layout::Expandable(_) => self
-                    .headers
-                    .get(i)
-                    .map(|h| h.as_ref().len() + (2 * self.padding))
-                    .unwrap_or(0),
+                Layout::Slim => padded(header.max(content)),
+                // known once widths of all other columns are
+                Layout::Percent(_) => 0,
+                Layout::Range { min, max } => {
+                    growable.push((i, 1, max));
+                    min
+                }
+                Layout::Fraction(weight) => {
+                    growable.push((i, weight, usize::MAX));
+                    padded(header)
+                }
+                // with content known, expandable column grows no wider than its content
+                Layout::Expandable(max_width) => {
+                    let cap = match content {
+                        Some(_) => max_width.min(padded(header.max(content))),
+                        None => max_width,
+                    };
+                    growable.push((i, 1, cap));
+                    padded(header)
+                }
             };
-            spec.width = column_width;
-            min_table_width += spec.width;
+            widths.push(column_width);
+        }
+
+        // percentage columns take their share of width left by other columns,
+        // with rounding remainders assigned by largest remainder. shares summing
+        // up to more than 100% get scaled down to fit. each column gets padding
+        // and a single character upfront, so it never gets narrower.
+        let percents = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| match c.layout {
+                Layout::Percent(p) if !hidden[i] => Some((i, p as usize, usize::MAX)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let total = percents.iter().map(|(_, p, _)| p).sum::<usize>().min(100);
+        let available = table_width.saturating_sub(widths.iter().sum::<usize>() + cols_count + 1);
+        let min_width = 2 * self.padding + 1;
+        for (i, _, _) in &percents {
+            widths[*i] = min_width;
+        }
+        distribute(
+            &mut widths,
+            &percents,
+            (available * total / 100).saturating_sub(percents.len() * min_width),
+        );
+
+        // calculate minimal table width with all paddings counted in
+        for (i, spec) in self.columns.iter().enumerate() {
+            if hidden[i] {
+                continue;
+            }
+            if spec.shrink > 0 {
+                let min_width = spec.min_width.unwrap_or(0).max(2 * self.padding + 1);
+                shrinkable.push((i, spec.shrink, widths[i].saturating_sub(min_width)));
+                min_table_width += widths[i].min(min_width);
+            } else {
+                min_table_width += widths[i];
+            }
         }
 
         min_table_width += cols_count + 1;
 
         // adjust columns widths so, that they will all sum up to desired `table_width`
-        // by calculating remaining width and distributing it equally (as much as possible)
-        // among all expandable columns.
-        let mut remaining_width = table_width.saturating_sub(min_table_width);
+        // by calculating remaining width and distributing it among all growable
+        // columns, proportionally to their weights.
+        let natural_width = widths.iter().sum::<usize>() + cols_count + 1;
+        distribute(
+            &mut widths,
+            &growable,
+            table_width.saturating_sub(natural_width),
+        );
 
-        if remaining_width > 0 {
-            let expandable_cols = self
+        // shrink columns, if table is too wide. when even minimal widths do not fit,
+        // all shrinkable columns end up at their minimal widths and table overflows.
+        let mut reductions = vec![0; hidden.len()];
+        distribute(
+            &mut reductions,
+            &shrinkable,
+            natural_width.saturating_sub(table_width),
+        );
+        for (width, reduction) in widths.iter_mut().zip(reductions) {
+            *width -= reduction;
+        }
+
+        (widths, min_table_width)
+    }
+
+    /// Returns columns widths and minimal table width to render given rows with.
+    fn widths_for<S: AsRef<str>, R: AsRef<[S]>>(&self, rows: &[R]) -> (Vec<Option<usize>>, usize) {
+        if !self.fit_content && !self.fit_width {
+            let widths = self
                 .columns
-                .iter_mut()
-                .filter_map(|c| match c.layout {
-                    Layout::Expandable(max_width) => Some((c, max_width)),
-                    _ => None,
-                })
-                .collect::<Vec<_>>();
-
-            let mut expandable_count = expandable_cols.len();
-            for (ec, max_width) in expandable_cols {
-                let new_width = compensate(ec.width, max_width, remaining_width / expandable_count);
-                let compensation = new_width.saturating_sub(ec.width);
-
-                if new_width > ec.width {
-                    ec.width = new_width;
-                }
-                remaining_width -= compensation;
-                expandable_count -= 1;
+                .iter()
+                .map(|c| (!c.hidden).then_some(c.width))
+                .collect();
+            return (widths, self.min_width);
+        }
+        let mut content = vec![0; self.columns.len()];
+        for row in rows {
+            for (i, cell) in row.as_ref().iter().enumerate().take(content.len()) {
+                let longest = cell.as_ref().lines().map(display_width).max();
+                content[i] = content[i].max(longest.unwrap_or(0));
+            }
+        }
+        for (i, decimal) in self.decimal_widths(rows).into_iter().enumerate() {
+            if let Some((int, frac)) = decimal {
+                content[i] = content[i].max(int + frac);
             }
         }
+        if self.fit_width {
+            let widths = self.content_widths(&content);
+            let width = widths.iter().sum::<usize>() + widths.len() + 1;
+            return (widths.into_iter().map(Some).collect(), width);
+        }
+        self.layout(self.width, Some(&content))
     }
 
-    fn generate_empty_string(&self, col_idx: usize, padding: usize) -> String {
-        if let Some(col) = self.columns.get(col_idx) {
-            let width = col.width.saturating_sub(2 * padding);
-            let mut result = String::with_capacity(width);
-            result.push_str(&" ".repeat(width));
-            return result;
-        }
-        String::default()
+    /// Returns widest integer and fractional parts of numbers in each of
+    /// decimal aligned columns.
+    pub(crate) fn decimal_widths<S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        rows: &[R],
+    ) -> Vec<Option<(usize, usize)>> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, col)| {
+                let Align::Decimal(separator) = col.align else {
+                    return None;
+                };
+                let parts = rows
+                    .iter()
+                    .filter_map(|row| row.as_ref().get(i))
+                    .flat_map(|cell| cell.as_ref().lines())
+                    .map(|line| decimal_parts(line, separator));
+                Some(parts.fold((0, 0), |(int, frac), (i, f)| (int.max(i), frac.max(f))))
+            })
+            .collect()
+    }
+
+    /// Calculates columns widths of table fit to its content, with no
+    /// requested width to fill up or fit into.
+    fn content_widths(&self, content: &[usize]) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, spec)| {
+                let header = self.header_width(i);
+                let padded = |w: Option<usize>| w.map(|w| w + (2 * self.padding)).unwrap_or(0);
+                let natural = padded(header.max(content.get(i).copied()));
+
+                match spec.layout {
+                    Layout::Fixed(width) => width,
+                    Layout::Expandable(max_width) => natural.min(max_width).max(padded(header)),
+                    Layout::Range { min, max } => natural.clamp(min, max.max(min)),
+                    _ => natural,
+                }
+            })
+            .collect()
+    }
+
+    fn header_width(&self, col_idx: usize) -> Option<usize> {
+        self.headers
+            .get(col_idx)
+            .and_then(Option::as_ref)
+            .map(|h| display_width(h.as_ref()))
+    }
+
+    fn generate_empty_string(&self, width: usize, padding: usize) -> String {
+        let width = width.saturating_sub(2 * padding);
+        let mut result = String::with_capacity(width);
+        result.push_str(&" ".repeat(width));
+        result
     }
 
     fn separator_chars(&self, separator: &Option<Separator>) -> (char, char, char, char) {
@@ -211,82 +617,220 @@ impl<'a, T: AsRef<str>> FancyTable<'a, T> {
         }
     }
 
-    fn render_row(&self, row: &'a [T]) {
+    /// Prepares cell content of given column to be broken into lines.
+    pub(crate) fn padstr<'s>(&'s self, col: &ColSpec, s: &'s str) -> PadStr<'s> {
+        let ellipsis = self.chars.ellipsis;
+        let padstr = match col.overflow {
+            // wrapping would lose spaces aligning decimal numbers
+            Overflow::Wrap if matches!(col.align, Align::Decimal(_)) => PadStr::truncating(s),
+            Overflow::Truncate => PadStr::truncating(s),
+            Overflow::Wrap => PadStr::wrapping(s)
+                .break_words_at(self.word_breaks)
+                .optimal_breaks(matches!(col.line_breaking, LineBreaking::Optimal))
+                .hyphenate(col.breakpoints()),
+            Overflow::WrapPreserve => PadStr::preserving(s, self.continuation.unwrap_or_default())
+                .break_words_at(self.word_breaks),
+            Overflow::Ellipsis => PadStr::eliding(s, Elide::End, ellipsis),
+            Overflow::EllipsisStart => PadStr::eliding(s, Elide::Start, ellipsis),
+            Overflow::EllipsisMiddle => PadStr::eliding(s, Elide::Middle, ellipsis),
+        };
+        match &self.more_lines {
+            MoreLines::Hidden => padstr,
+            MoreLines::Count => padstr.more_lines(More::Count(ellipsis)),
+            MoreLines::Marker(marker) => padstr.more_lines(More::Marker(marker)),
+        }
+    }
+
+    pub(crate) fn cell_style(&self, row_idx: Option<usize>, col_idx: usize) -> Style {
+        let styles = &self.styles;
+        match row_idx {
+            None => styles.header,
+            Some(r) => {
+                let col = self.columns[col_idx].style;
+                let row = styles.rows.get(&r).copied().unwrap_or_default();
+                let cell = styles.cells.get(&(r, col_idx)).copied().unwrap_or_default();
+                col.merge(&row).merge(&cell)
+            }
+        }
+    }
+
+    pub(crate) fn paint<'s>(&self, style: &Style, s: &'s str) -> Cow<'s, str> {
+        style.paint(s, self.color)
+    }
+
+    pub(crate) fn has_headers(&self) -> bool {
+        self.headers.iter().any(Option::is_some)
+    }
+
+    /// Returns headers of all columns, with empty ones for columns
+    /// created without a header.
+    pub(crate) fn header_row(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .map(|h| h.as_ref().map_or("", |h| h.as_ref()))
+            .collect()
+    }
+
+    fn render_row<W: fmt::Write, S: AsRef<str>>(
+        &self,
+        w: &mut W,
+        row: &[S],
+        row_idx: Option<usize>,
+        widths: &[Option<usize>],
+        decimals: &[Option<(usize, usize)>],
+    ) -> fmt::Result {
         let mut padded = row
             .iter()
+            .zip(widths)
             .enumerate()
-            .map(|(i, s)| {
+            .filter_map(|(i, (s, width))| {
+                let width = (*width)?;
                 let col = self.columns.get(i).unwrap();
                 let pad = match col.align {
                     Align::Left => Pad::Right,
-                    Align::Right => Pad::Left,
+                    Align::Right | Align::Decimal(_) => Pad::Left,
                     Align::Center => Pad::Center,
                 };
-                match col.overflow {
-                    Overflow::Truncate => PadStr::truncating(s.as_ref()),
-                    Overflow::Wrap => PadStr::wrapping(s.as_ref()),
-                }
-                .paddify(
-                    col.width.saturating_sub(2 * self.padding),
-                    col.max_lines,
-                    pad,
-                )
+                let hspace = width.saturating_sub(2 * self.padding);
+                let decimal = decimals.get(i).copied().flatten();
+                let s = decimal_aligned(col, s.as_ref(), decimal, hspace);
+                let lines = self.padstr(col, &s).paddify(hspace, col.max_lines, pad);
+                Some((i, width, lines))
             })
             .collect::<Vec<_>>();
 
-        let ns = self.chars.ns;
+        let ns = self
+            .paint(&self.styles.border, &self.chars.ns.to_string())
+            .into_owned();
         let len = padded.len();
-        let max_lines = padded.iter().map(|s| s.len()).max().unwrap_or(0);
+        let max_lines = padded.iter().map(|(_, _, s)| s.len()).max().unwrap_or(0);
         let str_padding = self.padding;
-        let edg_padding = self.padding + 1;
+        let edg_padding = self.padding;
+
+        for (i, width, vs) in padded.iter_mut() {
+            let valign = row_idx
+                .and_then(|row| self.row_valigns.get(&row))
+                .unwrap_or(&self.columns[*i].valign);
+            let missing = max_lines - vs.len();
+            let top = match valign {
+                VAlign::Top => 0,
+                VAlign::Middle => missing / 2,
+                VAlign::Bottom => missing,
+            };
+            for _ in 0..top {
+                vs.push_front(self.generate_empty_string(*width, str_padding));
+            }
+        }
 
         for _ in 0..max_lines {
-            print!("{:edg_padding$}", ns);
-            for (i, vs) in padded.iter_mut().enumerate() {
+            write!(w, "{ns}{:edg_padding$}", "")?;
+            for (n, (i, width, vs)) in padded.iter_mut().enumerate() {
                 let s = vs
                     .pop_front()
-                    .unwrap_or_else(|| self.generate_empty_string(i, str_padding));
-                print!("{s}");
-                if i < len - 1 {
-                    print!("{:>str_padding$}{ns}{:>str_padding$}", "", "");
+                    .unwrap_or_else(|| self.generate_empty_string(*width, str_padding));
+                write!(w, "{}", self.paint(&self.cell_style(row_idx, *i), &s))?;
+                if n < len - 1 {
+                    write!(w, "{:>str_padding$}{ns}{:>str_padding$}", "", "")?;
                 }
             }
-            println!("{:>edg_padding$}", ns);
+            writeln!(w, "{:>edg_padding$}{ns}", "")?;
+        }
+        Ok(())
+    }
+
+    /// Renders table with given rows to standard output.
+    pub fn render<R: AsRef<[T]>>(&self, rows: impl AsRef<[R]>) -> io::Result<()> {
+        self.render_to(&mut io::stdout().lock(), rows)
+    }
+
+    /// Renders table with given rows to any [`io::Write`] sink, like a file or socket.
+    pub fn render_to<W: io::Write, S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        w: &mut W,
+        rows: impl AsRef<[R]>,
+    ) -> io::Result<()> {
+        self.write_table_io(w, rows.as_ref())
+    }
+
+    /// Renders table into a String.
+    pub fn render_to_string<R: AsRef<[T]>>(&self, rows: impl AsRef<[R]>) -> String {
+        let mut out = String::new();
+        self.render_fmt(&mut out, rows)
+            .expect("writing to a String should never fail");
+        out
+    }
+
+    /// Renders table with given rows to any [`fmt::Write`] sink.
+    pub fn render_fmt<W: fmt::Write, S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        w: &mut W,
+        rows: impl AsRef<[R]>,
+    ) -> fmt::Result {
+        self.write_table(w, rows.as_ref())
+    }
+
+    pub(crate) fn write_table_io<W: io::Write, S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        w: &mut W,
+        rows: &[R],
+    ) -> io::Result<()> {
+        let mut writer = IoWriter {
+            inner: w,
+            error: Ok(()),
+        };
+        match self.write_table(&mut writer, rows) {
+            Ok(()) => Ok(()),
+            Err(_) => writer.error.and(Err(io::Error::other("formatter error"))),
         }
     }
 
-    pub fn render<R: AsRef<[T]>>(&self, rows: Vec<R>) {
+    pub(crate) fn write_table<W: fmt::Write, S: AsRef<str>, R: AsRef<[S]>>(
+        &self,
+        w: &mut W,
+        rows: &[R],
+    ) -> fmt::Result {
+        let (widths, min_width) = self.widths_for(rows);
+        let decimals = self.decimal_widths(rows);
+        let expanded = match self.expanded {
+            Expanded::Never => false,
+            Expanded::Always => true,
+            Expanded::Auto => !self.fit_width && min_width > self.width,
+        };
+        if expanded {
+            return self.render_expanded(w, rows);
+        }
+
         let ch = &self.chars;
-        let cols_count = self.columns.len();
+        let hidden_count = widths.iter().filter(|w| w.is_none()).count();
+        let widths_visible = widths.iter().flatten().copied().collect::<Vec<_>>();
+        let cols_count = widths_visible.len();
         let rows_count = rows.len();
         let rsep_chars = self.separator_chars(&self.rows_separator);
         let hsep_chars = self.separator_chars(&self.headers_separator);
-        let title_width = self
-            .title
-            .as_ref()
-            .map(|ts| ts.title.len() + 4)
-            .unwrap_or(0);
+        // borders follow actual columns widths, which may not add up
+        // to requested table width when columns could not expand.
+        let width = widths_visible.iter().sum::<usize>() + cols_count + 1;
 
         let mut acc = 1;
-        let mut border_top = vec![ch.ew; self.width];
-        let mut border_btm = vec![ch.ew; self.width];
-        let mut hseparator = vec![hsep_chars.0; self.width];
-        let mut rseparator = vec![rsep_chars.0; self.width];
+        let mut border_top = vec![ch.ew; width];
+        let mut border_btm = vec![ch.ew; width];
+        let mut hseparator = vec![hsep_chars.0; width];
+        let mut rseparator = vec![rsep_chars.0; width];
 
         border_top[0] = ch.se;
         border_btm[0] = ch.ne;
-        border_top[self.width - 1] = ch.sw;
-        border_btm[self.width - 1] = ch.nw;
+        border_top[width - 1] = ch.sw;
+        border_btm[width - 1] = ch.nw;
 
         hseparator[0] = hsep_chars.2;
         rseparator[0] = rsep_chars.2;
-        hseparator[self.width - 1] = hsep_chars.3;
-        rseparator[self.width - 1] = rsep_chars.3;
+        hseparator[width - 1] = hsep_chars.3;
+        rseparator[width - 1] = rsep_chars.3;
 
         // prepare top and bottom lines.
-        for (i, spec) in self.columns.iter().enumerate() {
+        for (i, column_width) in widths_visible.iter().enumerate() {
             if i < cols_count - 1 {
-                acc += spec.width + 1;
+                acc += column_width + 1;
                 border_top[acc - 1] = ch.ews;
                 border_btm[acc - 1] = ch.new;
                 hseparator[acc - 1] = hsep_chars.1;
@@ -294,52 +838,165 @@ impl<'a, T: AsRef<str>> FancyTable<'a, T> {
             }
         }
 
-        // draw a title
-        if title_width > 0 && title_width < self.width - 4 {
-            let spec = self.title.as_ref().unwrap();
-            let start = match spec.align {
-                TitleAlign::LeftOffset(lo) => lo + 1,
-                TitleAlign::RightOffset(ro) => self.width - ro - title_width - 1,
-            };
-            let end = start + title_width;
-            let tch = ch.title;
-            border_top.splice(start..end, format!("{tch} {} {tch}", spec.title).chars());
+        // note about hidden columns goes into bottom border, right aligned
+        // within the last column, so that no joint gets covered.
+        let note = match hidden_count {
+            1 => " +1 hidden column ".to_string(),
+            n => format!(" +{n} hidden columns "),
+        };
+        if self.hidden_note && hidden_count > 0 && acc + note.len() + 2 <= width {
+            let start = width - note.len() - 2;
+            border_btm.splice(start..start + note.len(), note.chars());
         }
 
-        let top = border_top.iter().collect::<String>();
-        let btm = border_btm.iter().collect::<String>();
-        let h_sep = hseparator.iter().collect::<String>();
-        let r_sep = rseparator.iter().collect::<String>();
+        let border = &self.styles.border;
+        let top = self.top_border(&border_top);
+        let btm = collect(&border_btm);
+        let h_sep = collect(&hseparator);
+        let r_sep = collect(&rseparator);
 
-        println!("{top}");
-        if !self.headers.is_empty() {
-            self.render_row(self.headers.as_slice());
+        writeln!(w, "{top}")?;
+        if self.has_headers() {
+            self.render_row(w, &self.header_row(), None, &widths, &[])?;
             if self.headers_separator.is_some() {
-                println!("{h_sep}");
+                writeln!(w, "{}", self.paint(border, &h_sep))?;
             }
         }
         for (i, r) in rows.iter().enumerate() {
-            self.render_row(r.as_ref());
+            self.render_row(w, r.as_ref(), Some(i), &widths, &decimals)?;
             if i < rows_count - 1 && self.rows_separator.is_some() {
-                println!("{r_sep}");
+                writeln!(w, "{}", self.paint(border, &r_sep))?;
             }
         }
-        println!("{btm}");
+        writeln!(w, "{}", self.paint(border, &btm))
+    }
+
+    /// Draws a title (if any) over the top border and paints the result.
+    pub(crate) fn top_border(&self, border_top: &[char]) -> String {
+        let width = border_top.len();
+        let border = &self.styles.border;
+        let title_width = self
+            .title
+            .as_ref()
+            .map(|ts| display_width(ts.title) + 4)
+            .unwrap_or(0);
+
+        let offset = match self.title.as_ref().map(|ts| &ts.align) {
+            Some(TitleAlign::LeftOffset(o) | TitleAlign::RightOffset(o)) => *o,
+            None => 0,
+        };
+        // title is left out when it does not fit between corners at its offset
+        if title_width > 0
+            && title_width < width.saturating_sub(4)
+            && offset + title_width + 2 <= width
+        {
+            let spec = self.title.as_ref().unwrap();
+            let start = match spec.align {
+                TitleAlign::LeftOffset(lo) => lo + 1,
+                TitleAlign::RightOffset(ro) => width - ro - title_width - 1,
+            };
+            let end = start + title_width;
+            let tch = self.chars.title;
+            let title = format!("{tch} {} {tch}", spec.title);
+
+            format!(
+                "{}{}{}",
+                self.paint(border, &collect(&border_top[..start])),
+                self.paint(&self.styles.title, &title),
+                self.paint(border, &collect(&border_top[end..]))
+            )
+        } else {
+            self.paint(border, &collect(border_top)).into_owned()
+        }
+    }
+}
+
+/// Pads content of decimal aligned column, so that separators of all its
+/// cells line up within `width` columns.
+pub(crate) fn decimal_aligned<'s>(
+    col: &ColSpec,
+    s: &'s str,
+    decimal: Option<(usize, usize)>,
+    width: usize,
+) -> Cow<'s, str> {
+    match (&col.align, decimal) {
+        (Align::Decimal(separator), Some((int, frac))) => {
+            Cow::Owned(align_decimal(s, *separator, int, frac, width))
+        }
+        _ => Cow::Borrowed(s),
+    }
+}
+
+pub(crate) fn collect(chars: &[char]) -> String {
+    chars.iter().collect()
+}
+
+/// Adapter passing formatted output down to [`io::Write`] and keeping
+/// the original I/O error, which [`fmt::Error`] is not able to carry.
+struct IoWriter<'w, W: io::Write> {
+    inner: &'w mut W,
+    error: io::Result<()>,
+}
+
+impl<W: io::Write> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Err(e);
+            fmt::Error
+        })
     }
 }
 
-fn compensate(width: usize, max_width: usize, compensation: usize) -> usize {
-    let compensated = width + compensation;
-    if compensated > max_width {
-        max_width
-    } else {
-        compensated
+/// Distributes `remaining` width among `growable` columns given as
+/// `(index, weight, max_width)`, proportionally to their weights.
+/// Shares which hit the maximal width flow over to other columns,
+/// rounding remainders go to columns with largest fractional part,
+/// rightmost first when equal.
+fn distribute(widths: &mut [usize], growable: &[(usize, usize, usize)], mut remaining: usize) {
+    let mut active = growable
+        .iter()
+        .filter(|(i, weight, max)| *weight > 0 && widths[*i] < *max)
+        .copied()
+        .collect::<Vec<_>>();
+
+    // columns which cannot take their whole share get maxed out first,
+    // which only increases shares of remaining ones.
+    loop {
+        let total_weight = active.iter().map(|(_, weight, _)| weight).sum::<usize>();
+        let Some(pos) = active.iter().position(|(i, weight, max)| {
+            remaining * weight >= (max - widths[*i]).saturating_mul(total_weight)
+        }) else {
+            break;
+        };
+        let (i, _, max) = active.remove(pos);
+        remaining -= max - widths[i];
+        widths[i] = max;
+    }
+
+    let total_weight = active.iter().map(|(_, weight, _)| weight).sum::<usize>();
+    if total_weight == 0 {
+        return;
+    }
+
+    let mut fractions = Vec::with_capacity(active.len());
+    let mut given = 0;
+    for (i, weight, _) in active {
+        let share = remaining * weight / total_weight;
+        widths[i] += share;
+        given += share;
+        fractions.push((remaining * weight % total_weight, i));
+    }
+
+    fractions.sort_by(|a, b| b.cmp(a));
+    for (_, i) in fractions.into_iter().take(remaining - given) {
+        widths[i] += 1;
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{style::Color, UNLIMITED_LINES};
 
     #[test]
     fn basic_constraints() {
@@ -380,4 +1037,527 @@ mod test {
         assert_eq!(table.columns.get(3).unwrap().width, 10);
         assert_eq!(table.columns.get(4).unwrap().width, 11);
     }
+
+    #[test]
+    fn render_into_string() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Fixed(4))
+            .add_column_named("NAME", Layout::Expandable(20))
+            .add_title("users")
+            .build(20);
+
+        let output = table.render_to_string(vec![["1", "Frodo"], ["2", "Sam"]]);
+        assert_eq!(
+            output,
+            "\
+╭────▪ users ▪─────╮
+│ ID │ NAME        │
+╞════╪═════════════╡
+│ 1  │ Frodo       │
+│ 2  │ Sam         │
+╰────┴─────────────╯
+"
+        );
+    }
+
+    #[test]
+    fn render_into_io_writer() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Fixed(4))
+            .add_column_named("NAME", Layout::Expandable(20))
+            .build(20);
+
+        let rows = [["1", "Frodo"]];
+        let mut buf = Vec::new();
+        table.render_to(&mut buf, rows).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            table.render_to_string(rows)
+        );
+    }
+
+    #[test]
+    fn render_propagates_io_errors() {
+        struct Broken;
+
+        impl io::Write for Broken {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::from(io::ErrorKind::BrokenPipe))
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Fixed(4))
+            .build(10);
+
+        let err = table.render_to(&mut Broken, [["1"]]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn mixed_script_rows_keep_borders_aligned() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ŻÓŁW", Layout::Slim)
+            .add_column_named("都市", Layout::Expandable(20))
+            .add_title("świat")
+            .build(24);
+
+        assert_eq!(table.columns.first().unwrap().width, 6);
+
+        let output = table.render_to_string([["Żółć", "東京"], ["👍", "Kraków"]]);
+        for line in output.lines() {
+            assert_eq!(display_width(line), 24, "misaligned line: {line}");
+        }
+    }
+
+    #[test]
+    fn render_with_styles() {
+        let table = FancyTable::create(FancyTableOpts {
+            charset: Charset::Simple,
+            headers_separator: None,
+            color: ColorMode::Always,
+            ..Default::default()
+        })
+        .add_column_named("ID", Layout::Fixed(4))
+        .add_column_named("OK", Layout::Fixed(4))
+        .add_title_with_align("t", TitleAlign::LeftOffset(0))
+        .header_style(Style::new().bold())
+        .column_style(1, Style::new().fg(Color::Green))
+        .cell_style(0, 1, Style::new().fg(Color::Red))
+        .border_style(Style::new().dim())
+        .title_style(Style::new().underline())
+        .build(11);
+
+        let output = table.render_to_string([["1", "no"], ["2", "yes"]]);
+        let expected = [
+            "\x1b[2m+\x1b[0m\x1b[4m* t *\x1b[0m\x1b[2m----+\x1b[0m",
+            "\x1b[2m|\x1b[0m \x1b[1mID\x1b[0m \x1b[2m|\x1b[0m \x1b[1mOK\x1b[0m \x1b[2m|\x1b[0m",
+            "\x1b[2m|\x1b[0m 1  \x1b[2m|\x1b[0m \x1b[31mno\x1b[0m \x1b[2m|\x1b[0m",
+            "\x1b[2m|\x1b[0m 2  \x1b[2m|\x1b[0m \x1b[32mye\x1b[0m \x1b[2m|\x1b[0m",
+            "\x1b[2m+----+----+\x1b[0m",
+        ];
+        assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn render_styles_ignored_without_color_mode() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Fixed(4))
+            .header_style(Style::new().bold())
+            .border_style(Style::new().fg(Color::Blue))
+            .build(6);
+
+        assert!(!table.render_to_string([["1"]]).contains('\x1b'));
+    }
+
+    #[test]
+    fn content_aware_layout() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Slim)
+            .add_column_named("NAME", Layout::Expandable(30))
+            .add_column_named("NOTE", Layout::Expandable(30))
+            .fit_content(true)
+            .padding(0)
+            .build(30);
+
+        let rows = [
+            ["12345", "Frodo", "Ring-bearer\nof the Shire"],
+            ["7", "Sam", ""],
+        ];
+        let (widths, min_width) = table.widths_for(&rows);
+
+        assert_eq!(widths, vec![Some(5), Some(5), Some(12)]);
+        assert_eq!(min_width, 5 + 4 + 4 + 4);
+        assert_eq!(
+            table.render_to_string(rows),
+            "\
+╭─────┬─────┬────────────╮
+│ID   │NAME │NOTE        │
+╞═════╪═════╪════════════╡
+│12345│Frodo│Ring-bearer │
+│     │     │of the Shire│
+│7    │Sam  │            │
+╰─────┴─────┴────────────╯
+"
+        );
+    }
+
+    #[test]
+    fn weighted_layouts() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Percent(10))
+            .add_column_named("NAME", Layout::Percent(25))
+            .add_column_named("ROLE", Layout::Range { min: 6, max: 8 })
+            .add_column_named("PERMISSION", Layout::Fraction(1))
+            .add_column_named("DESCRIPTION", Layout::Fraction(2))
+            .padding(0)
+            .build(80);
+
+        let widths = table.columns.iter().map(|c| c.width).collect::<Vec<_>>();
+        assert_eq!(widths, vec![5, 11, 8, 20, 30]);
+        assert_eq!(widths.iter().sum::<usize>() + 6, 80);
+    }
+
+    #[test]
+    fn percent_remainders_keep_table_width() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("A", Layout::Percent(33))
+            .add_column_named("B", Layout::Percent(33))
+            .add_column_named("C", Layout::Percent(34))
+            .build(25);
+
+        let widths = table.columns.iter().map(|c| c.width).collect::<Vec<_>>();
+        assert_eq!(widths, vec![7, 7, 7]);
+
+        let output = table.render_to_string([["1", "2", "3"]]);
+        for line in output.lines() {
+            assert_eq!(display_width(line), 25);
+        }
+    }
+
+    #[test]
+    fn percents_over_100_scaled_down() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("A", Layout::Percent(80))
+            .add_column_named("B", Layout::Percent(80))
+            .build(30);
+
+        let widths = table.columns.iter().map(|c| c.width).collect::<Vec<_>>();
+        assert_eq!(widths, vec![13, 14]);
+
+        let output = table.render_to_string([["1", "2"]]);
+        for line in output.lines() {
+            assert_eq!(display_width(line), 30);
+        }
+    }
+
+    #[test]
+    fn percents_of_width_left_by_other_columns() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Fixed(6))
+            .add_column_named("NAME", Layout::Percent(100))
+            .build(40);
+        let widths = table.columns.iter().map(|c| c.width).collect::<Vec<_>>();
+        assert_eq!(widths, vec![6, 31]);
+
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("A", Layout::Percent(5))
+            .add_column_named("B", Layout::Percent(95))
+            .build(20);
+        let widths = table.columns.iter().map(|c| c.width).collect::<Vec<_>>();
+        assert_eq!(widths, vec![4, 13]);
+
+        let output = table.render_to_string([["1", "2"]]);
+        for line in output.lines() {
+            assert_eq!(display_width(line), 20);
+        }
+    }
+
+    #[test]
+    fn distribute_by_weights() {
+        let mut widths = vec![0, 0, 0];
+        distribute(&mut widths, &[(0, 1, 2), (1, 1, 100), (2, 2, 100)], 11);
+        assert_eq!(widths, vec![2, 3, 6]);
+    }
+
+    #[test]
+    fn title_left_out_when_offset_does_not_fit() {
+        let build = |align| {
+            FancyTable::create(FancyTableOpts::default())
+                .add_column_named("ID", Layout::Slim)
+                .add_column_named("NAME", Layout::Slim)
+                .add_title_with_align("hobbits", align)
+                .build_fit_content()
+        };
+        let output = build(TitleAlign::RightOffset(12)).render_to_string([["1", "Sam"]]);
+        assert!(output.starts_with("╭────┬──────╮\n"));
+
+        let output = build(TitleAlign::LeftOffset(12)).render_to_string([["1", "Sam"]]);
+        assert!(output.starts_with("╭────┬──────╮\n"));
+    }
+
+    #[test]
+    fn columns_without_headers() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column(None, Layout::Slim, Align::Left, Overflow::Truncate, 1)
+            .add_column(
+                Some("ID"),
+                Layout::Fixed(6),
+                Align::Left,
+                Overflow::Truncate,
+                1,
+            )
+            .add_column(
+                None,
+                Layout::Expandable(12),
+                Align::Left,
+                Overflow::Truncate,
+                1,
+            )
+            .build(0);
+
+        let widths = table.columns.iter().map(|c| c.width).collect::<Vec<_>>();
+        assert_eq!(widths, vec![DEFAULT_COLUMN_WIDTH, 6, DEFAULT_COLUMN_WIDTH]);
+
+        let output = table.render_to_string([["Frodo", "1", "hobbit"]]);
+        for line in output.lines() {
+            assert_eq!(display_width(line), 30);
+        }
+    }
+
+    #[test]
+    fn min_width_keeps_padding() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("NAME", Layout::Fixed(10))
+            .column_shrink(0, 1)
+            .column_min_width(0, 1)
+            .build(5);
+
+        assert_eq!(table.columns[0].width, 3);
+        let output = table.render_to_string([["Frodo"]]);
+        for line in output.lines() {
+            assert_eq!(display_width(line), 5);
+        }
+    }
+
+    #[test]
+    fn shrink_columns_by_weights() {
+        let build = |width| {
+            FancyTable::create(FancyTableOpts::default())
+                .add_column_named("ID", Layout::Fixed(10))
+                .add_column_named("NAME", Layout::Fixed(10))
+                .add_column_named("ROLE", Layout::Fixed(6))
+                .column_shrink(0, 1)
+                .column_min_width(0, 4)
+                .column_shrink(1, 3)
+                .column_min_width(1, 4)
+                .padding(0)
+                .build(width)
+        };
+
+        let table = build(26);
+        let widths = table.columns.iter().map(|c| c.width).collect::<Vec<_>>();
+        assert_eq!(widths, vec![9, 7, 6]);
+        assert_eq!(table.min_width, 4 + 4 + 6 + 4);
+
+        // minimal widths do not fit, table overflows with borders matching columns
+        let table = build(10);
+        let widths = table.columns.iter().map(|c| c.width).collect::<Vec<_>>();
+        assert_eq!(widths, vec![4, 4, 6]);
+
+        let output = table.render_to_string([["1", "Frodo", "hobbit"]]);
+        for line in output.lines() {
+            assert_eq!(display_width(line), 18);
+        }
+    }
+
+    #[test]
+    fn hide_lowest_priority_columns() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Fixed(4))
+            .add_column_named("NAME", Layout::Fixed(20))
+            .add_column_named("ROLE", Layout::Fixed(8))
+            .add_column_named("NOTE", Layout::Fixed(8))
+            .column_priority(1, 5)
+            .column_priority(2, 1)
+            .column_priority(3, 1)
+            .hidden_note(true)
+            .build(30);
+
+        let hidden = table.columns.iter().map(|c| c.hidden).collect::<Vec<_>>();
+        assert_eq!(hidden, vec![false, false, true, true]);
+        assert_eq!(
+            table.render_to_string([["1", "Frodo", "hobbit", "ring"]]),
+            "\
+╭────┬────────────────────╮
+│ ID │ NAME               │
+╞════╪════════════════════╡
+│ 1  │ Frodo              │
+╰────┴ +2 hidden columns ─╯
+"
+        );
+    }
+
+    #[test]
+    fn hidden_note_keeps_column_joints() {
+        let build = |role, width| {
+            FancyTable::create(FancyTableOpts::default())
+                .add_column_named("ID", Layout::Fixed(4))
+                .add_column_named("NAME", Layout::Fixed(8))
+                .add_column_named("ROLE", Layout::Fixed(role))
+                .add_column_named("NOTE", Layout::Fixed(8))
+                .column_priority(3, 1)
+                .hidden_note(true)
+                .build(width)
+        };
+        assert_eq!(
+            build(20, 36).render_to_string([["1", "Frodo", "hobbit", "ring"]]),
+            "\
+╭────┬────────┬────────────────────╮
+│ ID │ NAME   │ ROLE               │
+╞════╪════════╪════════════════════╡
+│ 1  │ Frodo  │ hobbit             │
+╰────┴────────┴─ +1 hidden column ─╯
+"
+        );
+        let narrow = build(8, 24).render_to_string([["1", "Frodo", "hobbit", "ring"]]);
+        assert!(narrow.ends_with("╰────┴────────┴────────╯\n"));
+    }
+
+    #[test]
+    fn fit_table_to_content() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ID", Layout::Slim)
+            .add_column_named("NAME", Layout::Expandable(8))
+            .add_column_named("NOTE", Layout::Fraction(1))
+            .build_fit_content();
+
+        assert_eq!(
+            table.render_to_string([["1", "Frodo Baggins", "ring"]]),
+            "\
+╭────┬────────┬──────╮
+│ ID │ NAME   │ NOTE │
+╞════╪════════╪══════╡
+│ 1  │ Frodo  │ ring │
+╰────┴────────┴──────╯
+"
+        );
+    }
+
+    #[test]
+    fn mark_cells_cut_at_max_lines() {
+        let table = FancyTable::create(FancyTableOpts {
+            max_lines: 1,
+            more_lines: MoreLines::Count,
+            ..Default::default()
+        })
+        .add_column(None, Layout::Fixed(22), Align::Left, Overflow::Wrap, 1)
+        .add_column(
+            None,
+            Layout::Fixed(6),
+            Align::Left,
+            Overflow::Wrap,
+            UNLIMITED_LINES,
+        )
+        .build(0);
+
+        assert_eq!(
+            table.render_to_string([["one two three four five", "a b c"]]),
+            "\
+╭──────────────────────┬──────╮
+│ one two … (+1 line)  │ a b  │
+│                      │ c    │
+╰──────────────────────┴──────╯
+"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "hyphenation")]
+    fn hyphenate_wrapped_column() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named_wrapping("DESCRIPTION", Layout::Fixed(18))
+            .column_hyphenation(0, Language::English)
+            .build(0);
+
+        assert_eq!(
+            table.render_to_string([["Unexpectedly extensive hyphenation"]]),
+            "\
+╭──────────────────╮
+│ DESCRIPTION      │
+╞══════════════════╡
+│ Unexpectedly ex- │
+│ tensive hyphen-  │
+│ ation            │
+╰──────────────────╯
+"
+        );
+
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named_wrapping("DESCRIPTION", Layout::Fixed(18))
+            .column_hyphenation(0, Language::English)
+            .column_line_breaking(0, LineBreaking::Optimal)
+            .build(0);
+
+        assert_eq!(
+            table.render_to_string([["Unexpectedly extensive hyphenation"]]),
+            "\
+╭──────────────────╮
+│ DESCRIPTION      │
+╞══════════════════╡
+│ Unexpectedly ex- │
+│ tensive hyphen-  │
+│ ation            │
+╰──────────────────╯
+"
+        );
+    }
+
+    #[test]
+    fn vertically_aligned_cells() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column(None, Layout::Fixed(4), Align::Left, Overflow::Wrap, 1)
+            .add_column(None, Layout::Fixed(4), Align::Left, Overflow::Wrap, 1)
+            .add_column(None, Layout::Fixed(6), Align::Left, Overflow::Wrap, 5)
+            .column_valign(0, VAlign::Middle)
+            .column_valign(1, VAlign::Bottom)
+            .row_valign(1, VAlign::Top)
+            .build(0);
+
+        assert_eq!(
+            table.render_to_string([["1", "ok", "a b c d e"], ["2", "ok", "a b"]]),
+            "\
+╭────┬────┬──────╮
+│    │    │ a b  │
+│ 1  │    │ c d  │
+│    │ ok │ e    │
+│ 2  │ ok │ a b  │
+╰────┴────┴──────╯
+"
+        );
+    }
+
+    #[test]
+    fn decimal_aligned_column() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named("ITEM", Layout::Fixed(7))
+            .add_column_named_with_align("PRICE", Layout::Fixed(12), Align::Decimal('.'))
+            .build(0);
+
+        assert_eq!(
+            table.render_to_string([["ring", "1024.5"], ["cloak", "-3.125"], ["bread", "2 PLN"]]),
+            "\
+╭───────┬────────────╮
+│ ITEM  │      PRICE │
+╞═══════╪════════════╡
+│ ring  │   1024.5   │
+│ cloak │     -3.125 │
+│ bread │      2 PLN │
+╰───────┴────────────╯
+"
+        );
+    }
+
+    #[test]
+    fn decimal_aligned_styled_and_narrow_values() {
+        let table = FancyTable::create(FancyTableOpts::default())
+            .add_column_named_with_align("PRICE", Layout::Fixed(8), Align::Decimal('.'))
+            .add_column_named_with_align("TAX", Layout::Fixed(9), Align::Decimal('.'))
+            .build(0);
+
+        assert_eq!(
+            table.render_to_string([["1024.5", "\x1b[31m12\x1b[0m"], ["-3.125", "0.25"],]),
+            "\
+╭────────┬─────────╮
+│  PRICE │     TAX │
+╞════════╪═════════╡
+│ 1024.5 │   \x1b[31m12\x1b[0m    │
+│ -3.125 │    0.25 │
+╰────────┴─────────╯
+"
+        );
+    }
 }
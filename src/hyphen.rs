@@ -0,0 +1,57 @@
+use hypher::Lang;
+
+use crate::{padstr::Breakpoints, Language};
+
+impl Language {
+    pub(crate) fn breakpoints(&self) -> Breakpoints {
+        match self {
+            Self::English => |word| breakpoints(word, Lang::English),
+            Self::Polish => |word| breakpoints(word, Lang::Polish),
+        }
+    }
+}
+
+/// Returns byte offsets of hyphenation points of given word. Only its
+/// alphabetic core gets hyphenated, leaving out surrounding punctuation.
+/// Words with escape sequences or inner non-letters are never hyphenated.
+fn breakpoints(word: &str, lang: Lang) -> Vec<usize> {
+    let not_letter = |c: char| !c.is_alphabetic();
+    let core = word.trim_matches(not_letter);
+    if core.is_empty() || core.contains(not_letter) {
+        return Vec::new();
+    }
+    let start = word.len() - word.trim_start_matches(not_letter).len();
+    let mut offset = start;
+
+    hypher::hyphenate(core, lang)
+        .map(|syllable| {
+            offset += syllable.len();
+            offset
+        })
+        .filter(|&at| at < start + core.len())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::padstr::{Pad, PadStr};
+
+    #[test]
+    fn hyphenation_points() {
+        assert_eq!(breakpoints("extensive", Lang::English), vec![2, 5]);
+        assert_eq!(breakpoints("(extensive),", Lang::English), vec![3, 6]);
+        assert_eq!(breakpoints("well-known", Lang::English), vec![]);
+        assert_eq!(breakpoints("kolorowy", Lang::Polish), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn hyphenate_whole_words_once() {
+        let js = PadStr::wrapping("an incomprehensibilities test")
+            .hyphenate(Some(Language::English.breakpoints()));
+        assert_eq!(
+            js.paddify(16, 4, Pad::Right),
+            vec!["an incomprehen- ", "sibilities test "]
+        );
+    }
+}
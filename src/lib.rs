@@ -1,24 +1,105 @@
+use std::collections::HashMap;
+
+use cell::{Cell, CellFormat};
 use charset::{Charset, TableChars};
+use style::{ColorMode, Style};
+
+#[cfg(feature = "derive")]
+pub use fancy_table_derive::FancyRow;
 
+mod ansi;
+pub mod cell;
 pub mod charset;
+mod expanded;
+mod export;
 mod fancy;
+#[cfg(feature = "hyphenation")]
+mod hyphen;
 mod padstr;
+pub mod style;
+mod term;
+
+/// Value of `max_lines` never cutting cells content.
+pub const UNLIMITED_LINES: usize = usize::MAX;
 
 pub enum Layout {
     Slim,
     Fixed(usize),
     Expandable(usize),
+    /// Percentage of table width left by columns of other layouts (but
+    /// fractional ones). Percentages over 100 in total are scaled down
+    /// proportionally.
+    Percent(u8),
+    /// Share of leftover width, relative to weights of other fractional columns.
+    Fraction(usize),
+    Range {
+        min: usize,
+        max: usize,
+    },
 }
 
 pub enum Overflow {
+    /// Wraps text at whitespace, breaking words longer than a line.
+    /// Characters wider than the whole column can't be shown and get cut.
     Wrap,
     Truncate,
+    /// Wraps text keeping leading whitespace of each line, with continuation
+    /// lines indented as the line they come from (eg. code or JSON).
+    /// Tabs are expanded to spaces, with tab stops every 4 columns.
+    WrapPreserve,
+    /// Truncates text ending it with an ellipsis.
+    Ellipsis,
+    /// Truncates beginning of text, eg. leading directories of a path.
+    EllipsisStart,
+    /// Truncates middle of text, keeping both its beginning and end.
+    EllipsisMiddle,
+}
+
+/// Strategy of breaking wrapped text into lines.
+pub enum LineBreaking {
+    /// Puts as many words as possible into each line.
+    Greedy,
+    /// Balances lengths of all lines (but last one) to minimize raggedness
+    /// of their right edges, in Knuth-Plass fashion. Hyphenated words
+    /// get broken where it makes lines more even.
+    Optimal,
+}
+
+/// Language of patterns hyphenating words of wrapped columns.
+#[cfg(feature = "hyphenation")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Polish,
+}
+
+/// Indicator put at the end of last visible line of cell which does not
+/// fit into its `max_lines`.
+pub enum MoreLines {
+    /// Lines get cut silently.
+    Hidden,
+    /// Number of cut lines, eg. `… (+4 lines)`.
+    Count,
+    /// Custom marker, eg. `↓`.
+    Marker(String),
 }
 
 pub enum Align {
     Center,
     Left,
     Right,
+    /// Aligns numbers of all rows on their decimal separator (eg. `.`).
+    /// Numbers with no fractional part are aligned as if they ended
+    /// with the separator, their units (if any) are kept after it.
+    Decimal(char),
+}
+
+/// Vertical alignment of cells shorter than the tallest cell in a row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VAlign {
+    Top,
+    Middle,
+    Bottom,
 }
 
 pub enum TitleAlign {
@@ -32,23 +113,51 @@ pub enum Separator {
     Custom(char),
 }
 
+/// Vertical rendering of each row as a block of `HEADER │ value` lines.
+/// `Auto` switches to it when table does not fit into requested width.
+pub enum Expanded {
+    Never,
+    Always,
+    Auto,
+}
+
 pub struct FancyTableOpts {
     pub title_align: TitleAlign,
     pub charset: Charset,
     pub headers_separator: Option<Separator>,
     pub rows_separator: Option<Separator>,
     pub max_lines: usize,
+    pub more_lines: MoreLines,
+    pub color: ColorMode,
+    pub expanded: Expanded,
+    pub fit_content: bool,
+    /// Makes [`FancyTableBuilder::build_for_terminal`] fit table to its
+    /// content, when output is not a terminal (eg. piped to a file).
+    pub fit_when_piped: bool,
 }
 
 pub struct FancyTable<'a, T: AsRef<str>> {
     width: usize,
+    min_width: usize,
+    fit_width: bool,
     chars: TableChars,
+    charset: Charset,
     padding: usize,
     columns: Vec<ColSpec>,
-    headers: Vec<T>,
+    headers: Vec<Option<T>>,
     rows_separator: Option<Separator>,
     headers_separator: Option<Separator>,
     title: Option<TitleSpec<'a>>,
+    color: ColorMode,
+    expanded: Expanded,
+    fit_content: bool,
+    hidden_note: bool,
+    more_lines: MoreLines,
+    continuation: Option<&'a str>,
+    word_breaks: &'a str,
+    row_valigns: HashMap<usize, VAlign>,
+    null_marker: &'a str,
+    styles: TableStyles,
 }
 
 pub struct FancyTableBuilder<'a, T: AsRef<str>> {
@@ -57,18 +166,69 @@ pub struct FancyTableBuilder<'a, T: AsRef<str>> {
     rows_separator: Option<Separator>,
     headers_separator: Option<Separator>,
     charset: Charset,
-    headers: Vec<T>,
+    headers: Vec<Option<T>>,
     columns: Vec<ColSpec>,
     title: Option<&'a str>,
     title_align: TitleAlign,
+    color: ColorMode,
+    expanded: Expanded,
+    fit_content: bool,
+    fit_when_piped: bool,
+    hidden_note: bool,
+    more_lines: MoreLines,
+    continuation: Option<&'a str>,
+    word_breaks: &'a str,
+    row_valigns: HashMap<usize, VAlign>,
+    null_marker: &'a str,
+    styles: TableStyles,
+}
+
+/// Struct rendered as a table row, with each field in its own column.
+/// Usually derived with `#[derive(FancyRow)]` (`derive` feature).
+pub trait FancyRow {
+    /// Adds columns of all rendered fields to given builder.
+    fn add_columns<'a>(
+        builder: FancyTableBuilder<'a, &'static str>,
+    ) -> FancyTableBuilder<'a, &'static str>;
+
+    /// Turns struct into cells of a row, in order of its columns.
+    fn to_cells(&self) -> Vec<Cell>;
+
+    /// Creates builder of table with columns of this row, fit to content
+    /// of rendered rows.
+    fn table<'a>(opts: FancyTableOpts) -> FancyTableBuilder<'a, &'static str>
+    where
+        Self: Sized,
+    {
+        Self::add_columns(FancyTable::create(opts)).fit_content(true)
+    }
 }
 
 struct ColSpec {
     width: usize,
     max_lines: usize,
     align: Align,
+    valign: VAlign,
     layout: Layout,
     overflow: Overflow,
+    line_breaking: LineBreaking,
+    format: CellFormat,
+    #[cfg(feature = "hyphenation")]
+    hyphenation: Option<Language>,
+    style: Style,
+    shrink: usize,
+    min_width: Option<usize>,
+    priority: Option<u8>,
+    hidden: bool,
+}
+
+#[derive(Default)]
+struct TableStyles {
+    header: Style,
+    title: Style,
+    border: Style,
+    rows: HashMap<usize, Style>,
+    cells: HashMap<(usize, usize), Style>,
 }
 
 struct TitleSpec<'a> {
@@ -1,11 +1,24 @@
-use std::collections::VecDeque;
+use std::{borrow::Cow, collections::VecDeque};
 
-#[derive(Debug)]
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::ansi::{self, Segment, SgrState};
+
+#[derive(Clone, Copy, Debug)]
 enum Chunk<'a> {
     Word(&'a str),
     Term(&'a str),
+    /// Part of hyphenated word, ending a line with a hyphen.
+    Hyphen(&'a str),
 }
 
+/// Returns byte offsets of points given word may be hyphenated at.
+pub type Breakpoints = fn(&str) -> Vec<usize>;
+
+/// Chunk along with byte offsets of its hyphenation points.
+type Pending<'a> = (Chunk<'a>, Vec<usize>);
+
 #[derive(Clone)]
 pub enum Pad {
     Left,
@@ -13,17 +26,344 @@ pub enum Pad {
     Center,
 }
 
+/// Part of truncated text replaced with an ellipsis.
+#[derive(Clone, Copy)]
+pub enum Elide {
+    Start,
+    Middle,
+    End,
+}
+
+/// Indicator of lines cut off at given vertical space.
+#[derive(Clone, Copy)]
+pub enum More<'a> {
+    /// Number of cut lines, preceded by given ellipsis.
+    Count(&'a str),
+    Marker(&'a str),
+}
+
 pub struct PadStr<'a> {
     inner: VecDeque<Chunk<'a>>,
+    elide: Option<(Elide, &'a str)>,
+    more: Option<More<'a>>,
+    // continuation marker (possibly empty) of indentation-preserving wrapping
+    hanging: Option<&'a str>,
+    // preferred characters (possibly none) to break too long words after
+    breaks: Option<&'a str>,
+    optimal: bool,
+    hyphenation: Option<Breakpoints>,
+}
+
+/// Number of columns between tab stops of indentation-preserving wrapping.
+const TAB_WIDTH: usize = 4;
+
+/// Returns number of terminal columns occupied by given string.
+/// East Asian wide characters take 2 columns, combining marks,
+/// zero-width joiners and ANSI escape sequences take none.
+pub fn display_width(s: &str) -> usize {
+    ansi::segments(s)
+        .map(|seg| match seg {
+            Segment::Text(t) => t.width(),
+            Segment::Escape(_) => 0,
+        })
+        .sum()
+}
+
+/// Cuts given string on grapheme cluster boundary, so that the result
+/// takes no more than `width` terminal columns. Escape sequences are
+/// never cut in the middle.
+fn truncate_to_width(s: &str, width: usize) -> &str {
+    let mut acc = 0;
+    let mut offset = 0;
+    for seg in ansi::segments(s) {
+        if let Segment::Text(t) = seg {
+            for (idx, g) in t.grapheme_indices(true) {
+                acc += g.width();
+                if acc > width {
+                    return &s[..offset + idx];
+                }
+            }
+        }
+        offset += match seg {
+            Segment::Text(t) | Segment::Escape(t) => t.len(),
+        };
+    }
+    s
+}
+
+/// Cuts given string on grapheme cluster boundary, so that its tail
+/// takes no more than `width` terminal columns.
+fn tail_to_width(s: &str, width: usize) -> &str {
+    let mut rest = display_width(s);
+    let mut offset = 0;
+    for seg in ansi::segments(s) {
+        if let Segment::Text(t) = seg {
+            for (idx, g) in t.grapheme_indices(true) {
+                if rest <= width {
+                    return &s[offset + idx..];
+                }
+                rest -= g.width();
+            }
+        }
+        offset += match seg {
+            Segment::Text(t) | Segment::Escape(t) => t.len(),
+        };
+    }
+    &s[s.len()..]
+}
+
+/// Returns all escape sequences of given string, so that styles of
+/// elided text are still applied to the rest of it.
+fn escapes(s: &str) -> String {
+    ansi::segments(s)
+        .filter_map(|seg| match seg {
+            Segment::Escape(e) => Some(e),
+            Segment::Text(_) => None,
+        })
+        .collect()
+}
+
+/// Shortens given string to `width` terminal columns, replacing cut off
+/// part with `marker`.
+fn elide<'s>(s: &'s str, width: usize, elide: Elide, marker: &str) -> Cow<'s, str> {
+    let marker_width = display_width(marker);
+    if display_width(s) <= width || marker_width > width {
+        return Cow::Borrowed(truncate_to_width(s, width));
+    }
+    let space = width - marker_width;
+    let elided = match elide {
+        Elide::End => format!("{}{marker}", truncate_to_width(s, space)),
+        Elide::Start => {
+            let tail = tail_to_width(s, space);
+            let head = &s[..s.len() - tail.len()];
+            format!("{}{marker}{tail}", escapes(head))
+        }
+        Elide::Middle => {
+            let head = truncate_to_width(s, space - space / 2);
+            let rest = &s[head.len()..];
+            let tail = tail_to_width(rest, space / 2);
+            let mid = &rest[..rest.len() - tail.len()];
+            format!("{head}{marker}{}{tail}", escapes(mid))
+        }
+    };
+    Cow::Owned(elided)
+}
+
+/// Ends given line with `marker`, cutting the line if both don't fit
+/// into `width` columns.
+fn mark_line(s: &str, width: usize, marker: &str) -> String {
+    let marker_width = display_width(marker);
+    if marker_width >= width {
+        return truncate_to_width(marker, width).to_owned();
+    }
+    let s = truncate_to_width(s, width - marker_width - 1).trim_end();
+    if display_width(s) == 0 {
+        marker.to_owned()
+    } else {
+        format!("{s} {marker}")
+    }
+}
+
+/// Returns head of given word to be put into `width` columns, cut after
+/// the last of preferred `breaks` characters if there is any. Head is
+/// never empty, even if the first grapheme itself does not fit (it gets
+/// cut when padded then, as it can't be shown in `width` columns).
+fn word_head<'s>(word: &'s str, width: usize, breaks: &str) -> &'s str {
+    let mut head = truncate_to_width(word, width);
+    if display_width(head) == 0 {
+        head = first_grapheme(word);
+    }
+    if head.len() == word.len() || breaks.is_empty() {
+        return head;
+    }
+    let mut cut = None;
+    let mut offset = 0;
+    for seg in ansi::segments(head) {
+        if let Segment::Text(t) = seg {
+            if let Some((i, c)) = t.char_indices().rev().find(|(_, c)| breaks.contains(*c)) {
+                cut = Some(offset + i + c.len_utf8());
+            }
+        }
+        offset += match seg {
+            Segment::Text(t) | Segment::Escape(t) => t.len(),
+        };
+    }
+    cut.map_or(head, |cut| &head[..cut])
+}
+
+/// Returns given string up to the end of its first grapheme cluster,
+/// along with escape sequences preceding it.
+fn first_grapheme(s: &str) -> &str {
+    let mut offset = 0;
+    for seg in ansi::segments(s) {
+        match seg {
+            Segment::Text(t) => {
+                let g = t.graphemes(true).next().unwrap_or_default();
+                return &s[..offset + g.len()];
+            }
+            Segment::Escape(e) => offset += e.len(),
+        }
+    }
+    s
+}
+
+/// Replaces tabs of given indentation with spaces up to next tab stop.
+fn expand_tabs(indent: &str) -> String {
+    indent.chars().fold(String::new(), |mut agg, c| {
+        match c {
+            '\t' => agg.push_str(&" ".repeat(TAB_WIDTH - display_width(&agg) % TAB_WIDTH)),
+            c => agg.push(c),
+        }
+        agg
+    })
+}
+
+/// Tells whether every part of chunk between its hyphenation points fits
+/// into `hspace`, along with a hyphen ending it.
+fn hyphenated_fit((chunk, points): &Pending, hspace: usize) -> bool {
+    let word = chunk.inner();
+    let mut from = 0;
+    for &at in points.iter().filter(|&&p| p > 0 && p < word.len()) {
+        if display_width(&word[from..at]) + 1 > hspace {
+            return false;
+        }
+        from = at;
+    }
+    !points.is_empty() && chunk.with_inner(&word[from..]).width() <= hspace
+}
+
+/// Returns rest of chunk following its first `at` bytes, with hyphenation
+/// points moved accordingly.
+fn rest_of<'a>(chunk: Chunk<'a>, points: &[usize], at: usize) -> Pending<'a> {
+    let points = points.iter().filter(|&&p| p > at).map(|p| p - at).collect();
+    (chunk.with_inner(&chunk.inner()[at..]), points)
+}
+
+/// Extra cost of line ending with a hyphen, so that words get hyphenated
+/// only when it makes lines noticeably more even.
+const HYPHEN_COST: usize = 9;
+
+/// Breaks chunks into lines minimizing sum of squared spaces left at the
+/// end of each line (but last one) of every paragraph, along with cost of
+/// each line ending with a hyphenated word.
+fn optimal_lines<'a>(chunks: &[Pending<'a>], hspace: usize) -> Vec<(String, SgrState<'a>)> {
+    let mut bag = Vec::new();
+    let mut style = SgrState::default();
+
+    for paragraph in chunks.split_inclusive(|(c, _)| !matches!(c, Chunk::Word(_))) {
+        // parts of words between their hyphenation points, along with
+        // whether they get joined with next part by a hyphen.
+        let parts = paragraph
+            .iter()
+            .flat_map(|(chunk, points)| {
+                let word = chunk.inner();
+                let mut bounds = vec![0];
+                bounds.extend(points.iter().filter(|&&p| p > 0 && p < word.len()));
+                bounds.push(word.len());
+                bounds
+                    .windows(2)
+                    .map(|b| (chunk.with_inner(&word[b[0]..b[1]]), b[1] < word.len()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let n = parts.len();
+
+        // cost of the best breaking of first `j` parts, along with index
+        // of the first part in its last line.
+        let mut best = vec![(0usize, 0usize); n + 1];
+        for j in 1..=n {
+            best[j] = (usize::MAX, j - 1);
+            let hyphen = parts[j - 1].1;
+            let mut line_width = hyphen as usize;
+            for i in (0..j).rev() {
+                line_width += parts[i].0.width() + (i < j - 1 && !parts[i].1) as usize;
+                if line_width > hspace && i < j - 1 {
+                    break;
+                }
+                let slack = hspace.saturating_sub(line_width);
+                let cost = match (j == n, hyphen) {
+                    (true, _) => 0,
+                    (false, true) => slack * slack + HYPHEN_COST,
+                    (false, false) => slack * slack,
+                };
+                let total = best[i].0.saturating_add(cost);
+                if total < best[j].0 {
+                    best[j] = (total, i);
+                }
+            }
+        }
+
+        let mut lines = Vec::new();
+        let mut j = n;
+        while j > 0 {
+            let i = best[j].1;
+            lines.push(&parts[i..j]);
+            j = i;
+        }
+        for line_parts in lines.into_iter().rev() {
+            let line_style = style.clone();
+            let mut line = String::new();
+            for (k, (part, hyphen)) in line_parts.iter().enumerate() {
+                line.push_str(&part.text());
+                match hyphen {
+                    true if k == line_parts.len() - 1 => line.push('-'),
+                    true => {}
+                    false if k < line_parts.len() - 1 => line.push(' '),
+                    false => {}
+                }
+                style.feed(part.inner());
+            }
+            bag.push((line, line_style));
+        }
+    }
+    bag
+}
+
+/// Returns widths of integer and fractional part (including separator and
+/// units following the number) of given string. Escape sequences don't
+/// count in, as they take no width.
+pub fn decimal_parts(s: &str, separator: char) -> (usize, usize) {
+    let s = ansi::strip(s);
+    let split = s.find(separator).unwrap_or_else(|| {
+        let start = s.find(|c: char| c.is_ascii_digit()).unwrap_or(s.len());
+        let digits = s[start..]
+            .find(|c: char| !(c.is_ascii_digit() || c == ',' || c == '_'))
+            .unwrap_or(s.len() - start);
+        start + digits
+    });
+    (display_width(&s[..split]), display_width(&s[split..]))
+}
+
+/// Pads each line of given string with spaces, so that decimal separators
+/// of all lines end up at the same position, after `int_width` columns.
+/// Lines never get padded beyond `width`, so that numbers fitting into it
+/// are not cut, even if they can't be aligned.
+pub fn align_decimal(
+    s: &str,
+    separator: char,
+    int_width: usize,
+    frac_width: usize,
+    width: usize,
+) -> String {
+    s.lines()
+        .map(|line| {
+            let (int, frac) = decimal_parts(line, separator);
+            let room = width.saturating_sub(int + frac);
+            let right = frac_width.saturating_sub(frac).min(room);
+            let left = int_width.saturating_sub(int).min(room - right);
+            format!("{:>left$}{line}{:>right$}", "", "")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 fn should_wrap(agg: &str, s: &Chunk, hspace: usize) -> bool {
     let line_start = agg.is_empty();
-    !line_start && (agg.len() + (!line_start as usize) + s.inner().len() > hspace)
+    !line_start && (display_width(agg) + (!line_start as usize) + s.width() > hspace)
 }
 
 fn center_string(s: &str, width: usize) -> String {
-    let padding = width - s.chars().count();
+    let padding = width.saturating_sub(display_width(s));
     let left_padding = padding / 2;
     let right_padding = padding - left_padding;
 
@@ -31,25 +371,62 @@ fn center_string(s: &str, width: usize) -> String {
 }
 
 fn rightpad_string(s: &str, width: usize) -> String {
-    format!("{:width$}", s)
+    let padding = width.saturating_sub(display_width(s));
+    format!("{s}{:>padding$}", "")
 }
 
 fn leftpad_string(s: &str, width: usize) -> String {
-    format!("{:>width$}", s)
+    let padding = width.saturating_sub(display_width(s));
+    format!("{:>padding$}{s}", "")
 }
 
 impl<'a> Chunk<'a> {
     fn inner(&self) -> &'a str {
         match self {
-            Self::Word(s) | Self::Term(s) => s,
+            Self::Word(s) | Self::Term(s) | Self::Hyphen(s) => s,
+        }
+    }
+
+    /// Returns chunk of the same kind with different content.
+    fn with_inner(&self, s: &'a str) -> Self {
+        match self {
+            Self::Word(_) => Self::Word(s),
+            Self::Term(_) => Self::Term(s),
+            Self::Hyphen(_) => Self::Hyphen(s),
+        }
+    }
+
+    fn text(&self) -> Cow<'a, str> {
+        match self {
+            Self::Hyphen(s) => Cow::Owned(format!("{s}-")),
+            _ => Cow::Borrowed(self.inner()),
         }
     }
+
+    fn width(&self) -> usize {
+        display_width(self.inner()) + matches!(self, Self::Hyphen(_)) as usize
+    }
 }
 
 impl<'a> PadStr<'a> {
     pub fn truncating(s: &'a str) -> Self {
         let inner = s.lines().map(Chunk::Term).collect::<VecDeque<_>>();
-        Self { inner }
+        Self {
+            inner,
+            elide: None,
+            more: None,
+            hanging: None,
+            breaks: None,
+            optimal: false,
+            hyphenation: None,
+        }
+    }
+
+    pub fn eliding(s: &'a str, elide: Elide, marker: &'a str) -> Self {
+        Self {
+            elide: Some((elide, marker)),
+            ..Self::truncating(s)
+        }
     }
 
     pub fn wrapping(s: &'a str) -> Self {
@@ -68,43 +445,303 @@ impl<'a> PadStr<'a> {
             })
             .collect::<VecDeque<_>>();
 
-        Self { inner }
+        Self {
+            inner,
+            elide: None,
+            more: None,
+            hanging: None,
+            breaks: Some(""),
+            optimal: false,
+            hyphenation: None,
+        }
+    }
+
+    pub fn preserving(s: &'a str, continuation: &'a str) -> Self {
+        Self {
+            hanging: Some(continuation),
+            breaks: Some(""),
+            ..Self::truncating(s)
+        }
+    }
+
+    /// Makes words too long to fit into a line broken preferably after
+    /// one of given characters, eg. `/` in URLs.
+    pub fn break_words_at(self, chars: &'a str) -> Self {
+        Self {
+            breaks: self.breaks.map(|_| chars),
+            ..self
+        }
+    }
+
+    /// Makes wrapped lines balanced rather than greedily filled.
+    pub fn optimal_breaks(self, optimal: bool) -> Self {
+        Self { optimal, ..self }
+    }
+
+    /// Makes words not fitting into a line hyphenated, if possible.
+    pub fn hyphenate(self, hyphenation: Option<Breakpoints>) -> Self {
+        Self {
+            hyphenation,
+            ..self
+        }
+    }
+
+    pub fn more_lines(self, more: More<'a>) -> Self {
+        Self {
+            more: Some(more),
+            ..self
+        }
     }
 
     pub fn paddify(&self, hspace: usize, vspace: usize, pad: Pad) -> VecDeque<String> {
-        let mut bag = VecDeque::new();
+        let mut lines = self.lines(hspace);
+        let cut = lines.len().saturating_sub(vspace);
+        lines.truncate(vspace);
+
+        if let (Some(more), Some((line, _)), true) = (self.more, lines.last_mut(), cut > 0) {
+            let marker = match more {
+                More::Count(ellipsis) if cut == 1 => format!("{ellipsis} (+1 line)"),
+                More::Count(ellipsis) => format!("{ellipsis} (+{cut} lines)"),
+                More::Marker(marker) => marker.to_owned(),
+            };
+            *line = mark_line(line, hspace, &marker);
+        }
+        lines
+            .iter()
+            .map(|(line, style)| self.pad_str(line, style, hspace, &pad))
+            .collect()
+    }
+
+    /// Breaks content into lines fitting into `hspace`, along with styles
+    /// active at the beginning of each line.
+    fn lines(&self, hspace: usize) -> Vec<(String, SgrState<'a>)> {
+        if let Some(continuation) = self.hanging {
+            return self.hanging_lines(hspace, continuation);
+        }
+        if self.optimal {
+            let chunks = self
+                .inner
+                .iter()
+                .flat_map(|chunk| self.break_chunk(*chunk, hspace))
+                .collect::<Vec<_>>();
+            return optimal_lines(&chunks, hspace);
+        }
+        let mut chunks = self
+            .inner
+            .iter()
+            .map(|chunk| self.pending(*chunk))
+            .collect::<VecDeque<_>>();
+
+        let mut bag = Vec::new();
         let mut agg = String::default();
 
-        for (i, s) in self.inner.iter().enumerate() {
-            let last_str = i == self.inner.len() - 1;
-            let term_str = matches!(s, Chunk::Term(_));
+        // styles active after all processed chunks and at the beginning
+        // of currently aggregated line, so they can be re-opened there.
+        let mut style = SgrState::default();
+        let mut line_style = SgrState::default();
+
+        while let Some(pending) = chunks.pop_front() {
+            let mut s = pending.0;
+            let room = match agg.is_empty() {
+                true => hspace,
+                false => hspace.saturating_sub(display_width(&agg) + 1),
+            };
+            if self.breaks.is_some() && s.width() > room {
+                let split = match self.hyphenate_chunk(&pending, room) {
+                    Some(split) => Some(split),
+                    None if s.width() <= hspace => None,
+                    None if agg.is_empty() => Some(self.split_chunk(&pending, hspace)),
+                    None => {
+                        // word too long for any line starts a new one,
+                        // to be broken there.
+                        bag.push((std::mem::take(&mut agg), line_style.clone()));
+                        chunks.push_front(pending);
+                        continue;
+                    }
+                };
+                if let Some((head, rest)) = split {
+                    if !rest.0.inner().is_empty() {
+                        chunks.push_front(rest);
+                    }
+                    s = head;
+                }
+            }
+            let last_str = chunks.is_empty();
+            let term_str = !matches!(s, Chunk::Word(_));
 
-            if !should_wrap(&agg, s, hspace) {
-                if !agg.is_empty() {
+            if !should_wrap(&agg, &s, hspace) {
+                if agg.is_empty() {
+                    line_style = style.clone();
+                } else {
                     agg.push(' ');
                 }
-                agg.push_str(s.inner());
+                agg.push_str(&s.text());
             } else {
-                bag.push_back(self.pad_str(&agg, hspace, &pad));
-                agg = s.inner().to_owned();
+                bag.push((agg, line_style));
+                line_style = style.clone();
+                agg = s.text().into_owned();
             }
-            if bag.len() < vspace && (agg.len() == hspace || last_str || term_str) {
-                bag.push_back(self.pad_str(&agg, hspace, &pad));
+            style.feed(s.inner());
+
+            if display_width(&agg) == hspace || last_str || term_str {
+                bag.push((agg, line_style.clone()));
                 agg = String::default();
             }
-            if bag.len() == vspace {
-                return bag;
+        }
+        bag
+    }
+
+    /// Returns chunk along with its hyphenation points, computed once for
+    /// the whole word.
+    fn pending(&self, chunk: Chunk<'a>) -> Pending<'a> {
+        let points = match (self.breaks, self.hyphenation) {
+            (Some(_), Some(hyphenation)) => hyphenation(chunk.inner()),
+            _ => Vec::new(),
+        };
+        (chunk, points)
+    }
+
+    /// Splits word too long to fit into `hspace` into pieces, each one
+    /// but last ending a line. Words which may be hyphenated so that each
+    /// of their parts fits into a line are left to line breaking. Lines of
+    /// truncated text are never split.
+    fn break_chunk(&self, chunk: Chunk<'a>, hspace: usize) -> Vec<Pending<'a>> {
+        let mut rest = self.pending(chunk);
+        if self.breaks.is_none() {
+            return vec![rest];
+        }
+        let mut pieces = Vec::new();
+        while rest.0.width() > hspace && !hyphenated_fit(&rest, hspace) {
+            let (head, tail) = self.split_chunk(&rest, hspace);
+            pieces.push((head, Vec::new()));
+            rest = tail;
+        }
+        if pieces.is_empty() || !rest.0.inner().is_empty() {
+            pieces.push(rest);
+        }
+        pieces
+    }
+
+    /// Splits chunk so that its head fits into `width`, at hyphenation
+    /// point if possible or at grapheme boundary otherwise.
+    fn split_chunk(&self, pending: &Pending<'a>, width: usize) -> (Chunk<'a>, Pending<'a>) {
+        self.hyphenate_chunk(pending, width).unwrap_or_else(|| {
+            let (chunk, points) = pending;
+            let head = word_head(chunk.inner(), width, self.breaks.unwrap_or_default());
+            (Chunk::Term(head), rest_of(*chunk, points, head.len()))
+        })
+    }
+
+    /// Splits chunk at its last hyphenation point leaving head (along
+    /// with a hyphen) no wider than `width`.
+    fn hyphenate_chunk(
+        &self,
+        (chunk, points): &Pending<'a>,
+        width: usize,
+    ) -> Option<(Chunk<'a>, Pending<'a>)> {
+        let word = chunk.inner();
+        let at = points
+            .iter()
+            .rev()
+            .copied()
+            .find(|&at| at > 0 && display_width(&word[..at]) < width)?;
+
+        Some((Chunk::Hyphen(&word[..at]), rest_of(*chunk, points, at)))
+    }
+
+    /// Breaks each line separately, keeping its leading whitespace and
+    /// indenting continuation lines the same way. Words not fitting into
+    /// a line on their own get broken on grapheme boundaries.
+    fn hanging_lines(&self, hspace: usize, continuation: &str) -> Vec<(String, SgrState<'a>)> {
+        let mut bag = Vec::new();
+        let mut style = SgrState::default();
+
+        for chunk in &self.inner {
+            let line = chunk.inner();
+            let text = line.trim_start();
+            let mut indent = expand_tabs(&line[..line.len() - text.len()]);
+            if display_width(&indent) >= hspace {
+                indent = String::default();
+            }
+            let mut hang = match continuation {
+                "" => indent.clone(),
+                marker => format!("{indent}{marker} "),
+            };
+            if display_width(&hang) >= hspace {
+                hang = String::default();
             }
+
+            let mut agg = indent;
+            let mut line_style = style.clone();
+            let mut fresh = true;
+
+            // words along with whether they follow a tab rather than space
+            let words = text
+                .split(' ')
+                .flat_map(|s| s.split('\t').enumerate().map(|(i, w)| (i > 0, w)));
+
+            for (tab, mut word) in words {
+                loop {
+                    let used = display_width(&agg);
+                    let sep = match (fresh, tab) {
+                        (true, _) => 0,
+                        (false, true) => TAB_WIDTH - used % TAB_WIDTH,
+                        (false, false) => 1,
+                    };
+                    if used + sep + display_width(word) <= hspace {
+                        agg.push_str(&" ".repeat(sep));
+                        agg.push_str(word);
+                        style.feed(word);
+                        fresh = false;
+                        break;
+                    }
+                    if fresh {
+                        // word wider than the whole line, break it but
+                        // always move at least one grapheme forward.
+                        let room = hspace.saturating_sub(display_width(&agg));
+                        let head = word_head(word, room, self.breaks.unwrap_or_default());
+                        agg.push_str(head);
+                        style.feed(head);
+                        word = &word[head.len()..];
+                        if word.is_empty() {
+                            fresh = false;
+                            break;
+                        }
+                    }
+                    bag.push((
+                        std::mem::replace(&mut agg, hang.clone()),
+                        std::mem::replace(&mut line_style, style.clone()),
+                    ));
+                    fresh = true;
+                }
+            }
+            bag.push((agg, line_style));
         }
         bag
     }
 
-    fn pad_str(&self, s: &str, hspace: usize, just: &Pad) -> String {
-        let subs = s.get(0..hspace).unwrap_or(s);
+    fn pad_str(&self, s: &str, style: &SgrState, hspace: usize, just: &Pad) -> String {
+        let subs = match self.elide {
+            Some((elide, marker)) => self::elide(s, hspace, elide, marker),
+            None => Cow::Borrowed(truncate_to_width(s, hspace)),
+        };
+
+        // re-open styles carried over from previous lines and reset
+        // the ones still active at the end, so they don't leak into borders.
+        let mut end_style = style.clone();
+        end_style.feed(&subs);
+
+        let reset = if end_style.is_empty() {
+            ""
+        } else {
+            ansi::RESET
+        };
+        let subs = format!("{}{subs}{reset}", style.prefix());
+
         match just {
-            Pad::Left => leftpad_string(subs, hspace),
-            Pad::Right => rightpad_string(subs, hspace),
-            Pad::Center => center_string(subs, hspace),
+            Pad::Left => leftpad_string(&subs, hspace),
+            Pad::Right => rightpad_string(&subs, hspace),
+            Pad::Center => center_string(&subs, hspace),
         }
     }
 }
@@ -138,14 +775,14 @@ mod test {
     fn justify_center_no_enough_hspace() {
         let js = PadStr::wrapping("Ala ma kota");
         let lines = js.paddify(2, 3, Pad::Center);
-        assert_eq!(lines, vec!["Al", "ma", "ko"]);
+        assert_eq!(lines, vec!["Al", "a ", "ma"]);
     }
 
     #[test]
     fn justify_center_no_enough_hspace_and_vspace() {
         let js = PadStr::wrapping("Ala ma kota");
         let lines = js.paddify(1, 2, Pad::Center);
-        assert_eq!(lines, vec!["A", "m"]);
+        assert_eq!(lines, vec!["A", "l"]);
     }
 
     #[test]
@@ -196,4 +833,262 @@ mod test {
         let lines = js.paddify(8, 3, Pad::Center);
         assert_eq!(lines, vec!["Ala ma k", "A kot ma", "Ona go k"]);
     }
+
+    #[test]
+    fn display_width_of_mixed_scripts() {
+        assert_eq!(display_width("Zażółć"), 6);
+        assert_eq!(display_width("東京"), 4);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("👍"), 2);
+    }
+
+    #[test]
+    fn justify_wide_characters() {
+        let js = PadStr::wrapping("東京 大阪 Kraków");
+        let lines = js.paddify(10, 3, Pad::Center);
+        assert_eq!(lines, vec!["東京 大阪 ", "  Kraków  "]);
+    }
+
+    #[test]
+    fn justify_combining_characters() {
+        let js = PadStr::truncating("Cafe\u{301}\nZażółć");
+        let lines = js.paddify(8, 2, Pad::Left);
+        assert_eq!(lines, vec!["    Cafe\u{301}", "  Zażółć"]);
+    }
+
+    #[test]
+    fn truncate_on_grapheme_boundaries() {
+        assert_eq!(truncate_to_width("Zażółć gęślą", 4), "Zażó");
+        assert_eq!(truncate_to_width("東京大阪", 5), "東京");
+        assert_eq!(truncate_to_width("Cafe\u{301}s", 4), "Cafe\u{301}");
+        assert_eq!(truncate_to_width("👍👍", 1), "");
+    }
+
+    #[test]
+    fn truncate_multibyte_to_exact_hspace() {
+        let js = PadStr::truncating("Zażółć gęślą jaźń\n東京大阪");
+        let lines = js.paddify(5, 2, Pad::Left);
+        assert_eq!(lines, vec!["Zażół", " 東京"]);
+    }
+
+    #[test]
+    fn wrap_hard_break_multibyte_word() {
+        let js = PadStr::wrapping("東京大阪 ok");
+        let lines = js.paddify(3, 5, Pad::Right);
+        assert_eq!(lines, vec!["東 ", "京 ", "大 ", "阪 ", "ok "]);
+    }
+
+    #[test]
+    fn wrap_graphemes_wider_than_line() {
+        let js = PadStr::wrapping("\u{1100}\u{1100}\u{1100} ok");
+        assert_eq!(js.paddify(3, 5, Pad::Right), vec!["   ", "ok "]);
+
+        let js = PadStr::preserving("\u{1100}\u{1100}\u{1100}ok", "");
+        assert_eq!(js.paddify(3, 5, Pad::Right), vec!["   ", "ok "]);
+
+        let js = PadStr::wrapping("東京");
+        assert_eq!(js.paddify(1, 5, Pad::Right), vec![" ", " "]);
+    }
+
+    #[test]
+    fn escapes_are_zero_width() {
+        let js = PadStr::truncating("\x1b[1;32mok\x1b[0m");
+        let lines = js.paddify(4, 1, Pad::Left);
+        assert_eq!(lines, vec!["  \x1b[1;32mok\x1b[0m"]);
+    }
+
+    #[test]
+    fn styles_carried_across_wrapped_lines() {
+        let js = PadStr::wrapping("\x1b[31mAla ma kota\x1b[0m i psa");
+        let lines = js.paddify(6, 3, Pad::Right);
+        assert_eq!(
+            lines,
+            vec!["\x1b[31mAla ma\x1b[0m", "\x1b[31mkota\x1b[0m i", "psa   "]
+        );
+    }
+
+    #[test]
+    fn truncate_never_cuts_escapes() {
+        let js = PadStr::truncating("ab\x1b[38;5;208mcdef\nnext\x1b[0m");
+        let lines = js.paddify(3, 2, Pad::Right);
+        assert_eq!(
+            lines,
+            vec!["ab\x1b[38;5;208mc\x1b[0m", "\x1b[38;5;208mnex\x1b[0m"]
+        );
+    }
+
+    #[test]
+    fn elide_overflowing_lines() {
+        let path = "/usr/local/bin/fancy";
+        let lines = |elide| PadStr::eliding(path, elide, "…").paddify(10, 1, Pad::Right);
+        assert_eq!(lines(Elide::End), vec!["/usr/loca…"]);
+        assert_eq!(lines(Elide::Start), vec!["…bin/fancy"]);
+        assert_eq!(lines(Elide::Middle), vec!["/usr/…ancy"]);
+
+        let js = PadStr::eliding("short", Elide::Middle, "...");
+        assert_eq!(js.paddify(6, 1, Pad::Right), vec!["short "]);
+    }
+
+    #[test]
+    fn elide_wide_and_styled_text() {
+        assert_eq!(elide("東京大阪", 6, Elide::End, "..."), "東...");
+        assert_eq!(elide("東京大阪", 6, Elide::Start, "..."), "...阪");
+        assert_eq!(elide("東京大阪", 2, Elide::Start, "..."), "東");
+        assert_eq!(
+            elide("\x1b[31mabcdef\x1b[0m", 4, Elide::Start, "…"),
+            "\x1b[31m…def\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn mark_cut_lines() {
+        let js = PadStr::truncating("{\n  \"a\": 1,\n  \"b\": 2\n}").more_lines(More::Count("…"));
+        assert_eq!(
+            js.paddify(14, 2, Pad::Right),
+            vec!["{             ", "… (+2 lines)  "]
+        );
+        assert_eq!(
+            js.paddify(20, 2, Pad::Right),
+            vec!["{                   ", "  \"a\": … (+2 lines) "]
+        );
+        assert_eq!(js.paddify(20, 3, Pad::Right)[2], "  \"b\": 2 … (+1 line)");
+
+        let js = PadStr::wrapping("Ala ma kota").more_lines(More::Marker("↓"));
+        assert_eq!(js.paddify(6, 1, Pad::Right), vec!["Ala ↓ "]);
+        assert_eq!(js.paddify(6, 2, Pad::Right), vec!["Ala ma", "kota  "]);
+    }
+
+    #[test]
+    fn wrap_preserving_indentation() {
+        let json = "{\n  \"name\": \"fancy table\",\n\n  \"tags\": [\"a\", \"b\"]\n}";
+        let lines = PadStr::preserving(json, "").paddify(12, 10, Pad::Right);
+        assert_eq!(
+            lines,
+            vec![
+                "{           ",
+                "  \"name\":   ",
+                "  \"fancy    ",
+                "  table\",   ",
+                "            ",
+                "  \"tags\":   ",
+                "  [\"a\", \"b\"]",
+                "}           ",
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_preserving_tab_indentation() {
+        let code = "if ok {\n\ta\tb;\n\tlet x =\tvalue();\n  \tdone();\n}";
+        let lines = PadStr::preserving(code, "").paddify(16, 6, Pad::Right);
+        assert_eq!(
+            lines,
+            vec![
+                "if ok {         ",
+                "    a   b;      ",
+                "    let x =     ",
+                "    value();    ",
+                "    done();     ",
+                "}               ",
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_preserving_with_continuation_marker() {
+        let code = "fn main() {\n    println!(\"hello\");\n}";
+        let lines = PadStr::preserving(code, "↪").paddify(14, 5, Pad::Right);
+        assert_eq!(
+            lines,
+            vec![
+                "fn main() {   ",
+                "    println!(\"",
+                "    ↪ hello\");",
+                "}             ",
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_long_words_at_break_chars() {
+        let url = "see https://example.com/some/path_to/file.rs";
+        let lines = PadStr::wrapping(url).paddify(16, 5, Pad::Right);
+        assert_eq!(
+            lines,
+            vec![
+                "see             ",
+                "https://example.",
+                "com/some/path_to",
+                "/file.rs        "
+            ]
+        );
+        let lines = PadStr::wrapping(url)
+            .break_words_at("/-_.")
+            .paddify(16, 5, Pad::Right);
+        assert_eq!(
+            lines,
+            vec![
+                "see             ",
+                "https://example.",
+                "com/some/path_  ",
+                "to/file.rs      "
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap_with_optimal_breaks() {
+        let js = PadStr::wrapping("aaa bb cc ddddd");
+        let lines = js.paddify(6, 3, Pad::Right);
+        assert_eq!(lines, vec!["aaa bb", "cc    ", "ddddd "]);
+
+        let js = js.optimal_breaks(true);
+        let lines = js.paddify(6, 3, Pad::Right);
+        assert_eq!(lines, vec!["aaa   ", "bb cc ", "ddddd "]);
+
+        let js = PadStr::wrapping("\x1b[31maaa bb cc\x1b[0m ddddd").optimal_breaks(true);
+        let lines = js.paddify(6, 3, Pad::Right);
+        assert_eq!(
+            lines,
+            vec!["\x1b[31maaa\x1b[0m   ", "\x1b[31mbb cc\x1b[0m ", "ddddd "]
+        );
+    }
+
+    #[test]
+    fn hyphenate_words_at_breakpoints() {
+        let syllables: Breakpoints = |word| (3..word.len()).step_by(3).collect();
+        let js = PadStr::wrapping("Ala mamakotka i psa").hyphenate(Some(syllables));
+        let lines = js.paddify(8, 4, Pad::Right);
+        assert_eq!(lines, vec!["Ala mam-", "akotka i", "psa     "]);
+
+        let js = js.optimal_breaks(true);
+        let lines = js.paddify(8, 4, Pad::Right);
+        assert_eq!(lines, vec!["Ala mam-", "akotka i", "psa     "]);
+
+        let js = PadStr::wrapping("abc defghijk lm")
+            .hyphenate(Some(syllables))
+            .optimal_breaks(true);
+        assert_eq!(js.paddify(8, 4, Pad::Right), vec!["abc def-", "ghijk lm"]);
+        let js = js.hyphenate(None);
+        assert_eq!(
+            js.paddify(8, 4, Pad::Right),
+            vec!["abc     ", "defghijk", "lm      "]
+        );
+    }
+
+    #[test]
+    fn align_on_decimal_separator() {
+        assert_eq!(decimal_parts("-12.50", '.'), (3, 3));
+        assert_eq!(decimal_parts("1,024 ms", '.'), (5, 3));
+        assert_eq!(decimal_parts("$7", '.'), (2, 0));
+        assert_eq!(decimal_parts("n/a", '.'), (3, 0));
+
+        assert_eq!(decimal_parts("\x1b[31m12\x1b[0m", '.'), (2, 0));
+        assert_eq!(decimal_parts("\x1b[1m-1.5\x1b[0m", '.'), (2, 2));
+
+        assert_eq!(align_decimal("-12.5", '.', 4, 4, 8), " -12.5  ");
+        assert_eq!(align_decimal("3 ms", '.', 4, 4, 8), "   3 ms ");
+        assert_eq!(align_decimal("0,25\n10", ',', 2, 3, 5), " 0,25\n10   ");
+        assert_eq!(align_decimal("1024.5", '.', 4, 4, 7), "1024.5 ");
+    }
 }
@@ -0,0 +1,195 @@
+use std::borrow::Cow;
+
+use crate::ansi::RESET;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    Ansi256(u8),
+    Rgb(u8, u8, u8),
+}
+
+/// Decides whether styles get emitted as ANSI escape sequences at all.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    #[default]
+    Never,
+    Always,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    inverse: bool,
+}
+
+impl Color {
+    fn code(&self, background: bool) -> String {
+        let base = if background { 40 } else { 30 };
+        match self {
+            Self::Ansi256(n) => format!("{};5;{n}", base + 8),
+            Self::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", base + 8),
+            basic => {
+                let (offset, bright) = match basic {
+                    Self::Black => (0, false),
+                    Self::Red => (1, false),
+                    Self::Green => (2, false),
+                    Self::Yellow => (3, false),
+                    Self::Blue => (4, false),
+                    Self::Magenta => (5, false),
+                    Self::Cyan => (6, false),
+                    Self::White => (7, false),
+                    Self::BrightBlack => (0, true),
+                    Self::BrightRed => (1, true),
+                    Self::BrightGreen => (2, true),
+                    Self::BrightYellow => (3, true),
+                    Self::BrightBlue => (4, true),
+                    Self::BrightMagenta => (5, true),
+                    Self::BrightCyan => (6, true),
+                    _ => (7, true),
+                };
+                let bright = if bright { 60 } else { 0 };
+                (base + bright + offset).to_string()
+            }
+        }
+    }
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+    pub fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+    pub fn inverse(mut self) -> Self {
+        self.inverse = true;
+        self
+    }
+
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Layers `other` on top of this style. Colors set in `other` win,
+    /// text attributes get combined.
+    pub fn merge(&self, other: &Style) -> Style {
+        Style {
+            fg: other.fg.or(self.fg),
+            bg: other.bg.or(self.bg),
+            bold: self.bold || other.bold,
+            dim: self.dim || other.dim,
+            italic: self.italic || other.italic,
+            underline: self.underline || other.underline,
+            inverse: self.inverse || other.inverse,
+        }
+    }
+
+    fn prefix(&self) -> String {
+        let attrs = [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+            (self.inverse, "7"),
+        ];
+        let codes = attrs
+            .iter()
+            .filter(|(on, _)| *on)
+            .map(|(_, code)| code.to_string())
+            .chain(self.fg.map(|c| c.code(false)))
+            .chain(self.bg.map(|c| c.code(true)))
+            .collect::<Vec<_>>();
+
+        format!("\x1b[{}m", codes.join(";"))
+    }
+
+    /// Wraps given string into escape sequences of this style. Resets
+    /// embedded in the string re-open the style, so that it spans the
+    /// whole string.
+    pub(crate) fn paint<'s>(&self, s: &'s str, mode: ColorMode) -> Cow<'s, str> {
+        if mode == ColorMode::Never || self.is_plain() || s.is_empty() {
+            return Cow::Borrowed(s);
+        }
+        let prefix = self.prefix();
+        let inner = s.replace(RESET, &format!("{RESET}{prefix}"));
+        Cow::Owned(format!("{prefix}{inner}{RESET}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn style_escape_codes() {
+        let style = Style::new()
+            .bold()
+            .underline()
+            .fg(Color::Red)
+            .bg(Color::BrightBlue);
+        assert_eq!(style.prefix(), "\x1b[1;4;31;104m");
+
+        let style = Style::new().fg(Color::Ansi256(208)).bg(Color::Rgb(1, 2, 3));
+        assert_eq!(style.prefix(), "\x1b[38;5;208;48;2;1;2;3m");
+    }
+
+    #[test]
+    fn paint_only_when_enabled() {
+        let style = Style::new().fg(Color::Green);
+        assert_eq!(style.paint("ok", ColorMode::Never), "ok");
+        assert_eq!(style.paint("ok", ColorMode::Always), "\x1b[32mok\x1b[0m");
+        assert_eq!(
+            style.paint("a\x1b[0mb", ColorMode::Always),
+            "\x1b[32ma\x1b[0m\x1b[32mb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn merge_styles() {
+        let base = Style::new().fg(Color::Red).bold();
+        let merged = base.merge(&Style::new().fg(Color::Blue).italic());
+        assert_eq!(merged, Style::new().fg(Color::Blue).bold().italic());
+    }
+}
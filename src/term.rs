@@ -0,0 +1,53 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+/// Width assumed when it cannot be detected otherwise.
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+pub fn stdout_is_tty() -> bool {
+    io::stdout().is_terminal()
+}
+
+/// Returns width of terminal attached to stdout, falling back to `COLUMNS`
+/// environment variable when size cannot be queried.
+pub fn terminal_width() -> Option<usize> {
+    tty_width().or_else(|| columns_width(&env::var("COLUMNS").ok()?))
+}
+
+/// Parses width given by value of `COLUMNS` environment variable.
+fn columns_width(columns: &str) -> Option<usize> {
+    columns.trim().parse().ok().filter(|c| *c > 0)
+}
+
+#[cfg(unix)]
+fn tty_width() -> Option<usize> {
+    let mut ws = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: TIOCGWINSZ only writes into provided winsize struct.
+    let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut ws) };
+    (result == 0 && ws.ws_col > 0).then_some(ws.ws_col as usize)
+}
+
+#[cfg(not(unix))]
+fn tty_width() -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn width_from_columns_variable() {
+        assert_eq!(columns_width("132"), Some(132));
+        assert_eq!(columns_width(" 80\n"), Some(80));
+        assert_eq!(columns_width("0"), None);
+        assert_eq!(columns_width("wide"), None);
+    }
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "derive")]
+
+use fancy_table::{FancyRow, FancyTableOpts};
+
+#[derive(FancyRow)]
+struct Item {
+    #[table(layout = "fixed(7)")]
+    name: String,
+    #[table(header = "SIZE", layout = "fixed(10)", format(thousands = ' '))]
+    bytes: u64,
+    #[table(layout = "fixed(7)")]
+    ok: Option<bool>,
+    #[table(skip)]
+    _id: usize,
+}
+
+#[test]
+fn render_derived_rows() {
+    let items = [
+        Item {
+            name: "ring".into(),
+            bytes: 1,
+            ok: Some(true),
+            _id: 1,
+        },
+        Item {
+            name: "cloak".into(),
+            bytes: 123456,
+            ok: None,
+            _id: 2,
+        },
+    ];
+    let table = Item::table(FancyTableOpts::default())
+        .null_marker("?")
+        .build(0);
+
+    assert_eq!(
+        table.render_cells_to_string(items.iter().map(FancyRow::to_cells)),
+        "\
+╭───────┬──────────┬───────╮
+│ NAME  │     SIZE │  OK   │
+╞═══════╪══════════╪═══════╡
+│ ring  │        1 │ true  │
+│ cloak │  123 456 │   ?   │
+╰───────┴──────────┴───────╯
+"
+    );
+}
+
+#[derive(FancyRow)]
+struct Person {
+    name: String,
+    size: u64,
+}
+
+#[test]
+fn fit_default_columns_to_content() {
+    let people = [Person {
+        name: "Frodo Baggins".into(),
+        size: 12345,
+    }];
+    let table = Person::table(FancyTableOpts::default()).build(40);
+
+    assert_eq!(
+        table.render_cells_to_string(people.iter().map(FancyRow::to_cells)),
+        "\
+╭───────────────┬───────╮
+│ NAME          │  SIZE │
+╞═══════════════╪═══════╡
+│ Frodo Baggins │ 12345 │
+╰───────────────┴───────╯
+"
+    );
+}
+
+enum Race {
+    Hobbit,
+}
+
+impl std::fmt::Display for Race {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Race::Hobbit => write!(f, "hobbit"),
+        }
+    }
+}
+
+#[derive(FancyRow)]
+struct Character {
+    #[table(wrap, layout = "fixed(8)")]
+    name: &'static str,
+    #[table(display, align = "center", layout = "fixed(10)")]
+    race: Race,
+    #[table(format(precision = 1))]
+    height: f64,
+    #[table(header = "RAM", format = "units = \"iec\"")]
+    memory: u64,
+}
+
+#[test]
+fn render_derived_attributes() {
+    let characters = [Character {
+        name: "Samwise Gamgee",
+        race: Race::Hobbit,
+        height: 1.22,
+        memory: 1536,
+    }];
+    let table = Character::table(FancyTableOpts::default()).build(0);
+
+    assert_eq!(
+        table.render_cells_to_string(characters.iter().map(FancyRow::to_cells)),
+        "\
+╭────────┬──────────┬────────┬───────╮
+│ NAME   │   RACE   │ HEIGHT │   RAM │
+╞════════╪══════════╪════════╪═══════╡
+│ Samwis │  hobbit  │    1.2 │ 1.5Ki │
+│ e      │          │        │       │
+│ Gamgee │          │        │       │
+╰────────┴──────────┴────────┴───────╯
+"
+    );
+}
+
+#[derive(FancyRow)]
+struct Layouts {
+    #[table(layout = "expandable(12)")]
+    a: &'static str,
+    #[table(layout = "percent(20)")]
+    b: &'static str,
+    #[table(layout = "fraction(2)")]
+    c: &'static str,
+    #[table(layout = "range(4, 8)")]
+    d: &'static str,
+}
+
+#[test]
+fn render_derived_layouts() {
+    let rows = [Layouts {
+        a: "expandable",
+        b: "percent",
+        c: "fraction",
+        d: "range",
+    }];
+    let table = Layouts::table(FancyTableOpts::default()).build(60);
+
+    assert_eq!(
+        table.render_cells_to_string(rows.iter().map(FancyRow::to_cells)),
+        "\
+╭────────────┬─────────┬──────────────────────────┬────────╮
+│ A          │ B       │ C                        │ D      │
+╞════════════╪═════════╪══════════════════════════╪════════╡
+│ expandable │ percent │ fraction                 │ range  │
+╰────────────┴─────────┴──────────────────────────┴────────╯
+"
+    );
+}